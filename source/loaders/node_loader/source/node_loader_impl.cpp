@@ -446,9 +446,33 @@ typedef struct loader_impl_napi_to_value_callback_closure_type
 
 } * loader_impl_napi_to_value_callback_closure;
 
+typedef struct loader_impl_napi_future_closure_type
+{
+	loader_impl_node node_impl;
+	napi_deferred deferred;
+	napi_threadsafe_function threadsafe_settle;
+
+} * loader_impl_napi_future_closure;
+
+typedef struct loader_impl_napi_future_settle_type
+{
+	loader_impl_napi_future_closure closure;
+	value result;
+	bool resolved;
+
+} * loader_impl_napi_future_settle;
+
 /* Type conversion */
 static napi_value node_loader_impl_napi_to_value_callback(napi_env env, napi_callback_info info);
 
+static value node_loader_impl_future_resolve(value result, void *data);
+
+static value node_loader_impl_future_reject(value result, void *data);
+
+static void node_loader_impl_future_settle_safe(napi_env env, napi_value js_callback, void *context, void *data);
+
+static void node_loader_impl_future_finalize(napi_env env, void *finalize_data, void *finalize_hint);
+
 /* Function */
 static int function_node_interface_create(function func, function_impl impl);
 
@@ -1099,6 +1123,77 @@ napi_value node_loader_impl_napi_to_value_callback(napi_env env, napi_callback_i
 	return result;
 }
 
+static value node_loader_impl_future_settle(value result, void *data, bool resolved)
+{
+	loader_impl_napi_future_closure closure = static_cast<loader_impl_napi_future_closure>(data);
+	loader_impl_napi_future_settle settle = new loader_impl_napi_future_settle_type();
+
+	/* The result is owned by the caller, so it must be copied for settling the promise later on */
+	settle->closure = closure;
+	settle->result = value_type_copy(result);
+	settle->resolved = resolved;
+
+	/* The future can be settled from any thread, so the promise is settled in the JS thread */
+	napi_status status = napi_call_threadsafe_function(closure->threadsafe_settle, static_cast<void *>(settle), napi_tsfn_nonblocking);
+
+	if (status != napi_ok)
+	{
+		log_write("metacall", LOG_LEVEL_ERROR, "Invalid to call to thread safe function settle future in NodeJS loader");
+
+		value_type_destroy(settle->result);
+		delete settle;
+	}
+
+	/* Release the thread safe function, the closure is deleted when it is finalized */
+	status = napi_release_threadsafe_function(closure->threadsafe_settle, napi_tsfn_release);
+
+	if (status != napi_ok)
+	{
+		log_write("metacall", LOG_LEVEL_ERROR, "Invalid to release thread safe function settle future in NodeJS loader");
+	}
+
+	return NULL;
+}
+
+value node_loader_impl_future_resolve(value result, void *data)
+{
+	return node_loader_impl_future_settle(result, data, true);
+}
+
+value node_loader_impl_future_reject(value result, void *data)
+{
+	return node_loader_impl_future_settle(result, data, false);
+}
+
+void node_loader_impl_future_settle_safe(napi_env env, napi_value js_callback, void *context, void *data)
+{
+	loader_impl_napi_future_settle settle = static_cast<loader_impl_napi_future_settle>(data);
+
+	(void)js_callback;
+	(void)context;
+
+	/* The environment is NULL if the thread safe function is being torn down */
+	if (env != NULL)
+	{
+		napi_value v = node_loader_impl_value_to_napi(settle->closure->node_impl, env, settle->result);
+		napi_status status = settle->resolved ? napi_resolve_deferred(env, settle->closure->deferred, v) : napi_reject_deferred(env, settle->closure->deferred, v);
+
+		node_loader_impl_exception(env, status);
+	}
+
+	value_type_destroy(settle->result);
+
+	delete settle;
+}
+
+void node_loader_impl_future_finalize(napi_env env, void *finalize_data, void *finalize_hint)
+{
+	(void)env;
+	(void)finalize_hint;
+
+	delete static_cast<loader_impl_napi_future_closure>(finalize_data);
+}
+
 napi_value node_loader_impl_value_to_napi(loader_impl_node node_impl, napi_env env, value arg_value)
 {
 	type_id id = value_type_id(arg_value);
@@ -1244,8 +1339,33 @@ napi_value node_loader_impl_value_to_napi(loader_impl_node node_impl, napi_env e
 	}
 	else if (id == TYPE_FUTURE)
 	{
-		/* TODO: Implement promise properly for await */
-		napi_throw_error(env, NULL, "NodeJS Loader future is not implemented");
+		static const char settle_str[] = "node_loader_impl_future_settle";
+		loader_impl_napi_future_closure closure = new loader_impl_napi_future_closure_type();
+		napi_value settle_str_value;
+
+		closure->node_impl = node_impl;
+
+		status = napi_create_promise(env, &closure->deferred, &v);
+
+		node_loader_impl_exception(env, status);
+
+		status = napi_create_string_utf8(env, settle_str, sizeof(settle_str) - 1, &settle_str_value);
+
+		node_loader_impl_exception(env, status);
+
+		/* The thread safe function is released once the future is settled, then it deletes the closure */
+		status = napi_create_threadsafe_function(env, nullptr,
+			nullptr, settle_str_value,
+			0, 1,
+			static_cast<void *>(closure), &node_loader_impl_future_finalize,
+			nullptr, &node_loader_impl_future_settle_safe,
+			&closure->threadsafe_settle);
+
+		node_loader_impl_exception(env, status);
+
+		value ret = future_await(value_to_future(arg_value), &node_loader_impl_future_resolve, &node_loader_impl_future_reject, static_cast<void *>(closure));
+
+		value_type_destroy(ret);
 	}
 	else if (id == TYPE_FUNCTION)
 	{
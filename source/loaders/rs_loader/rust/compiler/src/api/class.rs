@@ -22,8 +22,16 @@ pub struct ClassInterface {
     static_set: extern "C" fn(OpaqueType, OpaqueType, OpaqueType, OpaqueType) -> c_int,
     static_invoke:
         extern "C" fn(OpaqueType, OpaqueType, OpaqueType, OpaqueTypeList, usize) -> OpaqueType,
-    static_await:
-        extern "C" fn(OpaqueType, OpaqueType, OpaqueType, OpaqueTypeList, usize) -> OpaqueType,
+    static_await: extern "C" fn(
+        OpaqueType,
+        OpaqueType,
+        OpaqueType,
+        OpaqueTypeList,
+        usize,
+        extern "C" fn(OpaqueType, OpaqueType) -> OpaqueType,
+        extern "C" fn(OpaqueType, OpaqueType) -> OpaqueType,
+        OpaqueType,
+    ) -> OpaqueType,
     destroy: extern "C" fn(OpaqueType, OpaqueType),
 }
 
@@ -93,28 +101,48 @@ extern "C" fn class_singleton_static_invoke(
         let name = CStr::from_ptr(method_name(method))
             .to_str()
             .expect("Unable to get method name");
-        let ret = class.call(name, args);
+        // async methods return a future, which runs the method when it is awaited
+        let ret = if method_async(method) == AsyncId::Asynchronous as c_int {
+            class.call_async(name, args).map(future::create_future)
+        } else {
+            class.call(name, args)
+        };
         std::mem::forget(class);
         std::mem::forget(name);
         ret
     };
-    if let Ok(ret) = ret {
-        return ret;
-    } else {
-        return 0 as OpaqueType;
-    }
+    ret.unwrap_or_else(|err| future::create_throwable(&err.to_string()))
 }
 
 #[no_mangle]
 extern "C" fn class_singleton_static_await(
     _klass: OpaqueType,
-    _class_impl: OpaqueType,
-    _method: OpaqueType,
-    _args_p: OpaqueTypeList,
-    _size: usize,
+    class_impl: OpaqueType,
+    method: OpaqueType,
+    args_p: OpaqueTypeList,
+    size: usize,
+    resolve: extern "C" fn(OpaqueType, OpaqueType) -> OpaqueType,
+    reject: extern "C" fn(OpaqueType, OpaqueType) -> OpaqueType,
+    data: OpaqueType,
 ) -> OpaqueType {
-    println!("class static await");
-    0 as OpaqueType
+    let ret = unsafe {
+        let class_impl_ptr = class_impl as *mut class::Class;
+        let class = Box::from_raw(class_impl_ptr);
+        let args = std::slice::from_raw_parts(args_p, size).to_vec();
+        let name = CStr::from_ptr(method_name(method))
+            .to_str()
+            .expect("Unable to get method name");
+        // sync methods are resolved with their return value
+        let ret = if method_async(method) == AsyncId::Asynchronous as c_int {
+            class.call_async(name, args).and_then(future::block_on)
+        } else {
+            class.call(name, args)
+        };
+        std::mem::forget(class);
+        std::mem::forget(name);
+        ret
+    };
+    future::settle(ret, resolve, reject, data)
 }
 
 #[no_mangle]
@@ -173,7 +201,7 @@ pub fn register_class(class_registration: ClassRegistration) {
         for (idx, arg) in constructor.args.iter().enumerate() {
             let name = CString::new(arg.name.clone())
                 .expect("Failed to convert function parameter name to C string");
-            let t = CString::new(arg.ty.to_string())
+            let t = CString::new(arg.type_name())
                 .expect("Failed to convert function parameter type to C string");
 
             unsafe {
@@ -194,8 +222,8 @@ pub fn register_class(class_registration: ClassRegistration) {
     for attr in class_info.attributes.iter() {
         let name =
             CString::new(attr.name.clone()).expect("Failed to convert function name to C string");
-        let ty = CString::new(attr.ty.ty.to_string())
-            .expect("Failed to convert function name to C string");
+        let ty =
+            CString::new(attr.ty.type_name()).expect("Failed to convert function name to C string");
         let attribute = unsafe {
             attribute_create(
                 class,
@@ -230,8 +258,8 @@ pub fn register_class(class_registration: ClassRegistration) {
 
         let s = unsafe { method_signature(m) };
         if let Some(ret) = &method.ret {
-            let ret = CString::new(ret.ty.to_string())
-                .expect("Failed to convert return type to C string");
+            let ret =
+                CString::new(ret.type_name()).expect("Failed to convert return type to C string");
 
             unsafe {
                 signature_set_return(
@@ -254,7 +282,7 @@ pub fn register_class(class_registration: ClassRegistration) {
         for (idx, param) in method.args.iter().enumerate() {
             let name = CString::new(param.name.clone())
                 .expect("Failed to convert function parameter name to C string");
-            let t = CString::new(param.ty.to_string())
+            let t = CString::new(param.type_name())
                 .expect("Failed to convert function parameter type to C string");
 
             unsafe {
@@ -278,15 +306,19 @@ pub fn register_class(class_registration: ClassRegistration) {
                 method.args.len(),
                 std::ptr::null_mut(),
                 0,
-                0,
+                if method.is_async {
+                    AsyncId::Asynchronous
+                } else {
+                    AsyncId::Synchronous
+                } as c_int,
                 std::ptr::null_mut(),
             )
         };
 
         let s = unsafe { method_signature(m) };
         if let Some(ret) = &method.ret {
-            let ret = CString::new(ret.ty.to_string())
-                .expect("Failed to convert return type to C string");
+            let ret =
+                CString::new(ret.type_name()).expect("Failed to convert return type to C string");
 
             unsafe {
                 signature_set_return(
@@ -309,7 +341,7 @@ pub fn register_class(class_registration: ClassRegistration) {
         for (idx, param) in method.args.iter().enumerate() {
             let name = CString::new(param.name.clone())
                 .expect("Failed to convert function parameter name to C string");
-            let t = CString::new(param.ty.to_string())
+            let t = CString::new(param.type_name())
                 .expect("Failed to convert function parameter type to C string");
 
             unsafe {
@@ -23,35 +23,70 @@ extern "C" fn function_singleton_create(_func: OpaqueType, _func_impl: OpaqueTyp
     0
 }
 
+fn is_async(func: OpaqueType) -> bool {
+    unsafe { function_async_id(func) == AsyncId::Asynchronous as c_int }
+}
+
 #[no_mangle]
 extern "C" fn function_singleton_invoke(
-    _func: OpaqueType,
+    func: OpaqueType,
     func_impl: OpaqueType,
     args_p: OpaqueTypeList,
     size: usize,
 ) -> OpaqueType {
     unsafe {
         let args = std::slice::from_raw_parts(args_p, size).to_vec();
-        let nf = Box::from_raw(func_impl as *mut class::NormalFunction);
-        let res = nf.invoke(args).expect("Function return error");
 
-        std::mem::forget(nf);
-        res
+        // async functions return a future, which runs the function when it is awaited
+        let res = if is_async(func) {
+            let af = Box::from_raw(func_impl as *mut class::AsyncFunction);
+            let res = af.invoke(args).map(future::create_future);
+
+            std::mem::forget(af);
+            res
+        } else {
+            let nf = Box::from_raw(func_impl as *mut class::NormalFunction);
+            let res = nf.invoke(args);
+
+            std::mem::forget(nf);
+            res
+        };
+
+        // the errors are thrown to the caller, panicking here would abort it
+        res.unwrap_or_else(|err| future::create_throwable(&err.to_string()))
     }
 }
 
 #[no_mangle]
 extern "C" fn function_singleton_await(
-    _func: OpaqueType,
-    _func_impl: OpaqueType,
-    _args: OpaqueTypeList,
-    _size: usize,
-    _resolve: extern "C" fn(OpaqueType, OpaqueType) -> OpaqueType,
-    _reject: extern "C" fn(OpaqueType, OpaqueType) -> OpaqueType,
-    _data: OpaqueType,
+    func: OpaqueType,
+    func_impl: OpaqueType,
+    args_p: OpaqueTypeList,
+    size: usize,
+    resolve: extern "C" fn(OpaqueType, OpaqueType) -> OpaqueType,
+    reject: extern "C" fn(OpaqueType, OpaqueType) -> OpaqueType,
+    data: OpaqueType,
 ) -> OpaqueType {
-    println!("rs_loader: await function");
-    0 as OpaqueType
+    let res = unsafe {
+        let args = std::slice::from_raw_parts(args_p, size).to_vec();
+
+        if is_async(func) {
+            let af = Box::from_raw(func_impl as *mut class::AsyncFunction);
+            let res = af.invoke(args).and_then(future::block_on);
+
+            std::mem::forget(af);
+            res
+        } else {
+            // sync functions are resolved with their return value
+            let nf = Box::from_raw(func_impl as *mut class::NormalFunction);
+            let res = nf.invoke(args);
+
+            std::mem::forget(nf);
+            res
+        }
+    };
+
+    future::settle(res, resolve, reject, data)
 }
 
 #[no_mangle]
@@ -95,6 +130,7 @@ pub struct FunctionRegistration {
     pub function_create: FunctionCreate,
     pub ret: Option<String>,
     pub input: Vec<FunctionInputSignature>,
+    pub is_async: bool,
 }
 
 pub fn register_function(function_registration: FunctionRegistration) {
@@ -109,6 +145,10 @@ pub fn register_function(function_registration: FunctionRegistration) {
     let name = CString::new(name).expect("Failed to convert function name to C string");
     let f = unsafe { function_create(name.as_ptr(), args_count, function_impl, singleton) };
 
+    if function_registration.is_async {
+        unsafe { function_async(f, AsyncId::Asynchronous as c_int) };
+    }
+
     let s = unsafe { function_signature(f) };
 
     if let Some(ret) = function_registration.ret {
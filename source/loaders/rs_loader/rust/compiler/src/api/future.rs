@@ -0,0 +1,147 @@
+use super::*;
+use crate::wrapper::class::{MetacallError, MetacallFuture};
+
+use std::{
+    ffi::CString,
+    os::raw::c_int,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+#[repr(C)]
+pub struct FutureInterface {
+    create: extern "C" fn(OpaqueType, OpaqueType) -> c_int,
+    r#await: extern "C" fn(
+        OpaqueType,
+        OpaqueType,
+        extern "C" fn(OpaqueType, OpaqueType) -> OpaqueType,
+        extern "C" fn(OpaqueType, OpaqueType) -> OpaqueType,
+        OpaqueType,
+    ) -> OpaqueType,
+    destroy: extern "C" fn(OpaqueType, OpaqueType),
+}
+
+// Wakes up the thread blocked on the future.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+// Embedded executor, it polls the future in the calling thread until it is ready.
+// The future is not sent to another thread because the callbacks of the caller
+// (i.e. the promises of NodeJS) must be resolved in the caller thread, and the
+// futures of the scripts are not Send. This has a limit: the caller thread is
+// parked until the future is ready, so the event loop of the caller (NodeJS,
+// Python) is blocked meanwhile, and a future that waits for something done by
+// that event loop never completes, deadlocking the caller.
+pub fn block_on(mut future: MetacallFuture) -> Result<OpaqueType, MetacallError> {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(result) => return result,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+// Creates the exception thrown to the caller when a call fails.
+pub fn create_throwable(message: &str) -> OpaqueType {
+    let message = CString::new(message).expect("Failed to convert error message to C string");
+    let label = CString::new("RustError").expect("Failed to convert error label to C string");
+
+    unsafe {
+        let exception =
+            exception_create_const(message.as_ptr(), label.as_ptr(), 0, std::ptr::null());
+
+        value_create_throwable(throwable_create(value_create_exception(exception)))
+    }
+}
+
+// Calls the callback with the value, which is owned by the loader, so it is destroyed afterwards.
+fn call_and_destroy(
+    callback: extern "C" fn(OpaqueType, OpaqueType) -> OpaqueType,
+    value: OpaqueType,
+    data: OpaqueType,
+) -> OpaqueType {
+    let ret = callback(value, data);
+
+    unsafe { value_type_destroy(value) };
+    ret
+}
+
+// Values of this type are the errors returned by the function, i.e. Err of a Result.
+const THROWABLE_TYPE_ID: c_int = 18;
+
+// Resolves with the returned value, or rejects with an exception if the call failed.
+pub fn settle(
+    result: Result<OpaqueType, MetacallError>,
+    resolve: extern "C" fn(OpaqueType, OpaqueType) -> OpaqueType,
+    reject: extern "C" fn(OpaqueType, OpaqueType) -> OpaqueType,
+    data: OpaqueType,
+) -> OpaqueType {
+    match result {
+        Ok(value) if unsafe { value_type_id(value) } == THROWABLE_TYPE_ID => {
+            call_and_destroy(reject, value, data)
+        }
+        Ok(value) => call_and_destroy(resolve, value, data),
+        Err(err) => call_and_destroy(reject, create_throwable(&err.to_string()), data),
+    }
+}
+
+#[no_mangle]
+extern "C" fn future_singleton_create(_future: OpaqueType, _future_impl: OpaqueType) -> c_int {
+    0
+}
+
+#[no_mangle]
+extern "C" fn future_singleton_await(
+    _future: OpaqueType,
+    future_impl: OpaqueType,
+    resolve: extern "C" fn(OpaqueType, OpaqueType) -> OpaqueType,
+    reject: extern "C" fn(OpaqueType, OpaqueType) -> OpaqueType,
+    data: OpaqueType,
+) -> OpaqueType {
+    let future = unsafe { &mut *(future_impl as *mut Option<MetacallFuture>) };
+
+    match future.take() {
+        Some(future) => settle(block_on(future), resolve, reject, data),
+        None => call_and_destroy(
+            reject,
+            create_throwable("Rust future has already been awaited"),
+            data,
+        ),
+    }
+}
+
+#[no_mangle]
+extern "C" fn future_singleton_destroy(_future: OpaqueType, future_impl: OpaqueType) {
+    if !future_impl.is_null() {
+        unsafe {
+            let future = Box::from_raw(future_impl as *mut Option<MetacallFuture>);
+            drop(future);
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn future_singleton() -> *const FutureInterface {
+    static SINGLETON: FutureInterface = FutureInterface {
+        create: future_singleton_create,
+        r#await: future_singleton_await,
+        destroy: future_singleton_destroy,
+    };
+
+    &SINGLETON
+}
+
+// Wraps the future into a MetaCall future, which runs it when it is awaited.
+pub fn create_future(future: MetacallFuture) -> OpaqueType {
+    let future_impl = Box::into_raw(Box::new(Some(future))) as OpaqueType;
+
+    unsafe { value_create_future(future_create(future_impl, future_singleton as OpaqueType)) }
+}
@@ -7,6 +7,7 @@ pub type OpaqueType = *mut c_void;
 pub type OpaqueTypeList = *mut OpaqueType;
 mod class;
 mod function;
+mod future;
 mod object;
 pub use function::{
     function_singleton, register_function, FunctionCreate, FunctionInputSignature,
@@ -66,6 +67,10 @@ extern "C" {
 
     fn value_create_function(function: OpaqueType) -> OpaqueType;
 
+    fn function_async(function: OpaqueType, async_id: c_int);
+
+    fn function_async_id(function: OpaqueType) -> c_int;
+
     fn value_type_destroy(v: OpaqueType);
 
     fn signature_set_return(signature: OpaqueType, t: OpaqueType);
@@ -110,6 +115,19 @@ extern "C" {
     fn class_register_method(class: OpaqueType, method: OpaqueType) -> c_int;
     fn method_signature(method: OpaqueType) -> OpaqueType;
     fn method_name(method: OpaqueType) -> *mut c_char;
+    fn method_async(method: OpaqueType) -> c_int;
+    fn future_create(future_impl: OpaqueType, singleton: OpaqueType) -> OpaqueType;
+    fn value_create_future(future: OpaqueType) -> OpaqueType;
+    fn exception_create_const(
+        message: *const c_char,
+        label: *const c_char,
+        code: i64,
+        stacktrace: *const c_char,
+    ) -> OpaqueType;
+    fn value_create_exception(exception: OpaqueType) -> OpaqueType;
+    fn throwable_create(value: OpaqueType) -> OpaqueType;
+    fn value_create_throwable(throwable: OpaqueType) -> OpaqueType;
+    fn value_type_id(v: OpaqueType) -> c_int;
     fn object_create(
         name: *const c_char,
         accessor_id: c_int,
@@ -154,6 +172,10 @@ pub enum PrimitiveMetacallProtocolTypes {
     Class = 15,
     Object = 16,
 }
+pub enum AsyncId {
+    Synchronous = 0,
+    Asynchronous = 1,
+}
 pub fn define_type(
     loader_impl: OpaqueType,
     name: &str,
@@ -1,6 +1,6 @@
 use super::rustc_ast::{
-    AngleBracketedArg, AngleBracketedArgs, FnRetTy, FnSig, GenericArg, GenericArgs, MutTy, Pat,
-    PatKind, TyKind,
+    AngleBracketedArg, AngleBracketedArgs, Async, FnRetTy, FnSig, GenericArg, GenericArgs, MutTy,
+    Pat, PatKind, TyKind,
 };
 use super::{Function, FunctionParameter, FunctionType, Mutability, Reference};
 
@@ -28,44 +28,35 @@ pub fn handle_ty(ty: &rustc_ast::Ty) -> FunctionParameter {
                 "f64" => result.ty = FunctionType::f64,
                 "bool" => result.ty = FunctionType::bool,
                 "str" => result.ty = FunctionType::String,
-                "Vec" => {
-                    result.ty = FunctionType::Array;
-                    if let Some(args) = &segment.args {
-                        match &**args {
-                            GenericArgs::AngleBracketed(AngleBracketedArgs { args, .. }) => {
-                                for arg in args {
-                                    match arg {
-                                        AngleBracketedArg::Arg(GenericArg::Type(ty)) => {
-                                            result.generic.push(handle_ty(ty))
-                                        }
-                                        _ => {}
-                                    }
-                                }
-                            }
-                            GenericArgs::Parenthesized(_) => {}
-                        }
-                    }
-                }
-                "HashMap" => {
-                    result.ty = FunctionType::Map;
-                    if let Some(args) = &segment.args {
-                        match &**args {
-                            GenericArgs::AngleBracketed(AngleBracketedArgs { args, .. }) => {
-                                for arg in args {
-                                    match arg {
-                                        AngleBracketedArg::Arg(GenericArg::Type(ty)) => {
-                                            result.generic.push(handle_ty(ty))
-                                        }
-                                        _ => {}
+                "Vec" => result.ty = FunctionType::Array,
+                "HashMap" => result.ty = FunctionType::Map,
+                "Option" => result.ty = FunctionType::Option,
+                "Result" => result.ty = FunctionType::Result,
+                "String" => result.ty = FunctionType::String,
+                _ => {}
+            }
+            if matches!(
+                result.ty,
+                FunctionType::Array
+                    | FunctionType::Map
+                    | FunctionType::Option
+                    | FunctionType::Result
+            ) {
+                if let Some(args) = &segment.args {
+                    match &**args {
+                        GenericArgs::AngleBracketed(AngleBracketedArgs { args, .. }) => {
+                            for arg in args {
+                                match arg {
+                                    AngleBracketedArg::Arg(GenericArg::Type(ty)) => {
+                                        result.generic.push(handle_ty(ty))
                                     }
+                                    _ => {}
                                 }
                             }
-                            GenericArgs::Parenthesized(_) => {}
                         }
+                        GenericArgs::Parenthesized(_) => {}
                     }
                 }
-                "String" => result.ty = FunctionType::String,
-                _ => {}
             }
             result.name = symbol_string;
         }
@@ -100,6 +91,7 @@ pub fn handle_fn(name: String, sig: &FnSig) -> Function {
         name,
         ret: None,
         args: vec![],
+        is_async: matches!(sig.header.asyncness, Async::Yes { .. }),
     };
     // parse input and output
     for arg in &sig.decl.inputs {
@@ -314,7 +314,9 @@ pub enum FunctionType {
     Ptr,
     Null,
     Complex,
-    This, // self in struct method
+    This,   // self in struct method
+    Option, // None is null
+    Result, // Err is thrown as an exception
 }
 
 impl fmt::Display for FunctionType {
@@ -333,11 +335,26 @@ pub struct FunctionParameter {
     generic: Vec<FunctionParameter>,
 }
 
+impl FunctionParameter {
+    // name of the type registered in the signature, Option and Result are registered
+    // as the type they wrap because None and Err do not have a type of their own
+    pub fn type_name(&self) -> String {
+        match self.ty {
+            FunctionType::Option | FunctionType::Result => match self.generic.first() {
+                Some(inner) => inner.type_name(),
+                None => FunctionType::Null.to_string(),
+            },
+            _ => self.ty.to_string(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct Function {
     name: String,
     ret: Option<FunctionParameter>,
     args: Vec<FunctionParameter>,
+    is_async: bool, // the return type is the output of the future
 }
 
 impl Function {
@@ -471,13 +488,10 @@ impl CompilerCallbacks {
                             }
                         }
                         Res::Def(DefKind::Fn, def_id) => {
-                            // https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/ty/struct.Binder.html
-                            let fn_sig = ctxt.fn_sig(*def_id);
-                            let names = ctxt.fn_arg_names(*def_id);
                             self.functions.push(middle::handle_fn(
+                                &ctxt,
                                 ident.to_string(),
-                                &fn_sig,
-                                names,
+                                *def_id,
                             ));
                         }
                         _ => {}
@@ -1,11 +1,13 @@
 use crate::Attribute;
 
 use super::rustc_middle::ty::{
-    subst::GenericArgKind, Binder, FloatTy, FnSig, IntTy, TyCtxt, TyKind, TyS, UintTy, Visibility,
+    subst::GenericArgKind, FloatTy, IntTy, PredicateKind, Ty, TyCtxt, TyKind, TyS, UintTy,
+    Visibility,
 };
-use super::rustc_span::symbol::Ident;
 use super::{Function, FunctionParameter, FunctionType, Mutability, Reference};
 use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::DefId;
+use rustc_hir::IsAsync;
 use rustc_middle::hir::exports::Export;
 use std::iter::zip;
 
@@ -66,6 +68,21 @@ pub fn handle_ty(ty: &TyS) -> FunctionParameter {
                         println!("expect value, get nothing");
                     }
                 }
+                "std::option::Option" => {
+                    result.ty = FunctionType::Option;
+                    if let GenericArgKind::Type(ty) = gen[0].unpack() {
+                        result.generic.push(handle_ty(ty));
+                    }
+                }
+                "std::result::Result" => {
+                    result.ty = FunctionType::Result;
+                    if let GenericArgKind::Type(ty) = gen[0].unpack() {
+                        result.generic.push(handle_ty(ty));
+                    }
+                    if let GenericArgKind::Type(ty) = gen[1].unpack() {
+                        result.generic.push(handle_ty(ty));
+                    }
+                }
                 "std::string::String" => result.ty = FunctionType::String,
                 _ => result.ty = FunctionType::Null,
             }
@@ -75,11 +92,28 @@ pub fn handle_ty(ty: &TyS) -> FunctionParameter {
     result
 }
 
-pub fn handle_fn(name: String, sig: &Binder<FnSig>, names: &[Ident]) -> Function {
+// async functions return `impl Future<Output = T>`, T is found in the bounds of the opaque type.
+fn future_output<'tcx>(ctxt: &TyCtxt<'tcx>, ty: Ty<'tcx>) -> Option<Ty<'tcx>> {
+    let TyKind::Opaque(def_id, _) = ty.kind() else {
+        return None;
+    };
+    ctxt.explicit_item_bounds(*def_id)
+        .iter()
+        .find_map(|(predicate, _)| match predicate.kind().skip_binder() {
+            PredicateKind::Projection(projection) => Some(projection.ty),
+            _ => None,
+        })
+}
+
+pub fn handle_fn(ctxt: &TyCtxt, name: String, def_id: DefId) -> Function {
+    // https://doc.rust-lang.org/stable/nightly-rustc/rustc_middle/ty/struct.Binder.html
+    let sig = ctxt.fn_sig(def_id);
+    let names = ctxt.fn_arg_names(def_id);
     let mut function = Function {
         name,
         ret: None,
         args: vec![],
+        is_async: matches!(ctxt.asyncness(def_id), IsAsync::Async),
     };
     // parse input and output
     let inputs = sig.inputs().skip_binder();
@@ -88,7 +122,12 @@ pub fn handle_fn(name: String, sig: &Binder<FnSig>, names: &[Ident]) -> Function
         func_parameter.name = name.to_string();
         function.args.push(func_parameter);
     }
-    let output = sig.output().skip_binder();
+    let mut output = sig.output().skip_binder();
+    if function.is_async {
+        if let Some(ty) = future_output(ctxt, output) {
+            output = ty;
+        }
+    }
     match output.kind() {
         TyKind::Tuple(arg) => {
             // default return
@@ -131,11 +170,7 @@ pub fn extract_fn_from_export(ctxt: &TyCtxt, export: &Export) -> Option<Function
         return None;
     }
     match res {
-        Res::Def(DefKind::AssocFn, def_id) => {
-            let fn_sig = ctxt.fn_sig(*def_id);
-            let names = ctxt.fn_arg_names(*def_id);
-            Some(handle_fn(ident.to_string(), &fn_sig, names))
-        }
+        Res::Def(DefKind::AssocFn, def_id) => Some(handle_fn(ctxt, ident.to_string(), *def_id)),
         _ => None,
     }
 }
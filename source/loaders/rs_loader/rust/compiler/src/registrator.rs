@@ -10,10 +10,17 @@ fn function_create(func: &Function, dlopen_library: &DlopenLibrary) -> FunctionC
     let args_count = func.args.len();
 
     let register_func_name = format!("metacall_register_fn_{}", name);
-    let register_func: unsafe fn() -> *mut class::NormalFunction =
-        unsafe { dlopen_library.instance.symbol(&register_func_name[..]) }
-            .expect(format!("Unable to find register function {}", name).as_str());
-    let function_impl = unsafe { register_func() } as OpaqueType;
+    let function_impl = if func.is_async {
+        let register_func: unsafe fn() -> *mut class::AsyncFunction =
+            unsafe { dlopen_library.instance.symbol(&register_func_name[..]) }
+                .expect(format!("Unable to find register function {}", name).as_str());
+        unsafe { register_func() as OpaqueType }
+    } else {
+        let register_func: unsafe fn() -> *mut class::NormalFunction =
+            unsafe { dlopen_library.instance.symbol(&register_func_name[..]) }
+                .expect(format!("Unable to find register function {}", name).as_str());
+        unsafe { register_func() as OpaqueType }
+    };
     FunctionCreate {
         name,
         args_count,
@@ -50,7 +57,7 @@ pub fn register(
             loader_impl,
             function_create: function_create(func, &dlopen_library),
             ret: match &func.ret {
-                Some(ret) => Some(ret.ty.to_string().clone()),
+                Some(ret) => Some(ret.type_name()),
                 _ => None,
             },
             input: func
@@ -58,9 +65,10 @@ pub fn register(
                 .iter()
                 .map(|param| FunctionInputSignature {
                     name: param.name.clone(),
-                    t: param.ty.to_string().clone(),
+                    t: param.type_name(),
                 })
                 .collect(),
+            is_async: func.is_async,
         };
 
         register_function(function_registration);
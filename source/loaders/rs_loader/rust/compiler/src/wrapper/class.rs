@@ -6,8 +6,10 @@ use std::collections::HashMap;
 use std::convert::TryInto;
 use std::ffi::{CStr, CString};
 use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
-type Result<T, E = i32> = core::result::Result<T, E>;
+type Result<T, E = MetacallError> = core::result::Result<T, E>;
 use std::os::raw::{c_char, c_double, c_float, c_int, c_long, c_short, c_void};
 extern "C" {
     fn value_type_count(v: *mut c_void) -> c_int;
@@ -36,14 +38,46 @@ extern "C" {
     fn metacall_value_create_array(values: *const *mut c_void, size: usize) -> *mut c_void;
     fn metacall_value_create_map(tuples: *const *mut c_void, size: usize) -> *mut c_void;
     fn metacall_value_create_null() -> *mut c_void;
+    fn metacall_error_from_value(v: *mut c_void, ex: *mut MetacallException) -> c_int;
+    fn exception_create_const(
+        message: *const c_char,
+        label: *const c_char,
+        code: i64,
+        stacktrace: *const c_char,
+    ) -> *mut c_void;
+    fn value_create_exception(ex: *mut c_void) -> *mut c_void;
+    fn throwable_create(v: *mut c_void) -> *mut c_void;
+    fn value_create_throwable(th: *mut c_void) -> *mut c_void;
+}
+
+#[repr(C)]
+struct MetacallException {
+    message: *const c_char,
+    label: *const c_char,
+    code: i64,
+    stacktrace: *const c_char,
 }
 
 type Attributes = HashMap<&'static str, AttributeGetter>;
 type AttributeSetters = HashMap<&'static str, AttributeSetter>;
 type ClassMethods = HashMap<&'static str, ClassMethod>;
 type InstanceMethods = HashMap<&'static str, InstanceMethod>;
+type AsyncClassMethods = HashMap<&'static str, AsyncFunction>;
 pub type MetacallValue = *mut c_void;
 
+/// Error of the conversion of the values, its message is thrown to the caller.
+#[derive(Debug, Clone)]
+pub struct MetacallError(String);
+
+impl fmt::Display for MetacallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The future of an async function, it resolves to the converted return value.
+pub type MetacallFuture = Pin<Box<dyn Future<Output = Result<MetacallValue>>>>;
+
 #[derive(Clone)]
 pub struct Class {
     /// The class name. Defaults to the `std::any::type_name`
@@ -54,6 +88,7 @@ pub struct Class {
     attr_setters: AttributeSetters,
     instance_methods: InstanceMethods,
     pub class_methods: ClassMethods,
+    async_class_methods: AsyncClassMethods,
 }
 
 impl Class {
@@ -73,11 +108,24 @@ impl Class {
         let attr = self
             .class_methods
             .get(attr)
-            .expect(format!("Unable to get {} from {}", attr, self.name).as_str());
+            .ok_or_else(|| self.missing_method(attr))?;
 
         attr.clone().invoke(args)
     }
 
+    pub fn call_async(&self, attr: &str, args: Vec<MetacallValue>) -> Result<MetacallFuture> {
+        let attr = self
+            .async_class_methods
+            .get(attr)
+            .ok_or_else(|| self.missing_method(attr))?;
+
+        attr.invoke(args)
+    }
+
+    fn missing_method(&self, attr: &str) -> MetacallError {
+        MetacallError(format!("Unable to get {} from {}", attr, self.name))
+    }
+
     fn get_method(&self, name: &str) -> Option<InstanceMethod> {
         self.instance_methods.get(name).cloned()
     }
@@ -105,6 +153,7 @@ where
                 attr_setters: AttributeSetters::new(),
                 instance_methods: InstanceMethods::new(),
                 class_methods: ClassMethods::new(),
+                async_class_methods: AsyncClassMethods::new(),
                 type_id: TypeId::of::<T>(),
             },
             ty: std::marker::PhantomData,
@@ -176,6 +225,13 @@ where
         self
     }
 
+    pub fn add_unsupported_method(mut self, name: &'static str, message: &'static str) -> Self {
+        self.class
+            .instance_methods
+            .insert(name, InstanceMethod::unsupported(message));
+        self
+    }
+
     pub fn add_class_method<F, Args, R>(mut self, name: &'static str, f: F) -> Self
     where
         F: Function<Args, Result = R>,
@@ -185,6 +241,19 @@ where
         self.class.class_methods.insert(name, ClassMethod::new(f));
         self
     }
+
+    pub fn add_async_class_method<F, Args, R>(mut self, name: &'static str, f: F) -> Self
+    where
+        F: Function<Args, Result = R>,
+        Args: FromMetaList + std::fmt::Debug,
+        R: Future + 'static,
+        R::Output: ToMetaResult,
+    {
+        self.class
+            .async_class_methods
+            .insert(name, AsyncFunction::new(f));
+        self
+    }
 }
 #[derive(Clone)]
 pub struct Instance {
@@ -421,6 +490,13 @@ impl InstanceMethod {
         ))
     }
 
+    // Method that can't be called, it throws the message instead.
+    pub fn unsupported(message: &'static str) -> Self {
+        Self(Arc::new(move |_: &Instance, _: Vec<MetacallValue>| {
+            Result::<(), _>::Err(message).to_meta_result()
+        }))
+    }
+
     pub fn invoke(&self, receiver: &Instance, args: Vec<MetacallValue>) -> Result<MetacallValue> {
         self.0(receiver, args)
     }
@@ -472,6 +548,32 @@ impl NormalFunction {
     }
 }
 
+/// Function or static method declared as `async fn`. The arguments are converted when it's
+/// called, and the returned future has to be polled by the loader.
+#[derive(Clone)]
+pub struct AsyncFunction(TypeErasedFunction<MetacallFuture>);
+
+impl AsyncFunction {
+    pub fn new<F, Args>(f: F) -> Self
+    where
+        Args: FromMetaList + std::fmt::Debug,
+        F: Function<Args>,
+        F::Result: Future + 'static,
+        <F::Result as Future>::Output: ToMetaResult,
+    {
+        Self(Arc::new(move |args: Vec<MetacallValue>| {
+            Args::from_meta_list(&args).map(|args| {
+                let res = f.invoke(args);
+                Box::pin(async move { res.await.to_meta_result() }) as MetacallFuture
+            })
+        }))
+    }
+
+    pub fn invoke(&self, args: Vec<MetacallValue>) -> Result<MetacallFuture> {
+        self.0(args)
+    }
+}
+
 pub trait ToMetaResult {
     fn to_meta_result(self) -> Result<MetacallValue>;
 }
@@ -593,6 +695,45 @@ where
         }
     }
 }
+impl<T> ToMetaResult for Option<T>
+where
+    T: ToMetaResult,
+{
+    fn to_meta_result(self) -> Result<MetacallValue> {
+        match self {
+            Some(val) => val.to_meta_result(),
+            None => Ok(unsafe { metacall_value_create_null() }),
+        }
+    }
+}
+
+impl<T, E> ToMetaResult for Result<T, E>
+where
+    T: ToMetaResult,
+    E: fmt::Display,
+{
+    fn to_meta_result(self) -> Result<MetacallValue> {
+        match self {
+            Ok(val) => val.to_meta_result(),
+            // the error is returned as a throwable, so the caller gets it as an exception
+            Err(err) => {
+                let message =
+                    CString::new(err.to_string()).expect("Unable to cast error to CString");
+                let label = CString::new("RustError").expect("Unable to cast label to CString");
+                Ok(unsafe {
+                    let exception = exception_create_const(
+                        message.as_ptr(),
+                        label.as_ptr(),
+                        0,
+                        std::ptr::null(),
+                    );
+                    value_create_throwable(throwable_create(value_create_exception(exception)))
+                })
+            }
+        }
+    }
+}
+
 pub trait FromMetaList {
     fn from_meta_list(values: &[MetacallValue]) -> Result<Self>
     where
@@ -632,6 +773,10 @@ enum PrimitiveMetacallProtocolTypes {
     Double = 6,
 }
 
+const METACALL_NULL: c_int = 14;
+const METACALL_EXCEPTION: c_int = 17;
+const METACALL_THROWABLE: c_int = 18;
+
 use std::convert::TryFrom;
 
 impl TryFrom<i32> for PrimitiveMetacallProtocolTypes {
@@ -676,10 +821,10 @@ macro_rules! convert_to {
                 Ok(PrimitiveMetacallProtocolTypes::Double) => {
                     Ok(metacall_value_to_double($val) as $t)
                 }
-                Err(_) => {
-                    println!("receive id: {}, should be [2-6]", id);
-                    panic!("received mismatch type");
-                }
+                Err(_) => Err(MetacallError(format!(
+                    "received mismatch type, id {} should be [2-6]",
+                    id
+                ))),
             }
         }
     };
@@ -780,6 +925,49 @@ where
     }
 }
 
+impl<T> FromMeta for Option<T>
+where
+    T: Clone + FromMeta,
+{
+    fn from_meta(val: MetacallValue) -> Result<Self> {
+        if unsafe { value_type_id(val) } == METACALL_NULL {
+            Ok(None)
+        } else {
+            T::from_meta(val).map(Some)
+        }
+    }
+}
+
+impl<T, E> FromMeta for Result<T, E>
+where
+    T: Clone + FromMeta,
+    E: Clone + From<String>,
+{
+    fn from_meta(val: MetacallValue) -> Result<Self> {
+        let id = unsafe { value_type_id(val) };
+        // exceptions and throwables are passed as the error, any other value as the result
+        if id == METACALL_EXCEPTION || id == METACALL_THROWABLE {
+            let mut exception = MetacallException {
+                message: std::ptr::null(),
+                label: std::ptr::null(),
+                code: 0,
+                stacktrace: std::ptr::null(),
+            };
+            if unsafe { metacall_error_from_value(val, &mut exception) } != 0 {
+                return Err(MetacallError(String::from(
+                    "received a throwable without exception",
+                )));
+            }
+            let message = unsafe { CStr::from_ptr(exception.message) }
+                .to_string_lossy()
+                .into_owned();
+            Ok(Err(E::from(message)))
+        } else {
+            T::from_meta(val).map(Ok)
+        }
+    }
+}
+
 // impl FromMeta for &mut Vec<i32> {
 //     fn from_meta(val: MetacallValue) -> Result<Self> {
 //         Ok(unsafe {
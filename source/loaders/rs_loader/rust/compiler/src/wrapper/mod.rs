@@ -2,19 +2,48 @@ pub mod class;
 use super::{config::Input, source_map::FileName::Custom, CompilerCallbacks, Function, Source};
 use std::fs::File;
 use std::io::Write;
+// async functions are wrapped into their own type, as they return a future
+fn function_wrapper_type(func: &Function) -> &'static str {
+    if func.is_async {
+        "AsyncFunction"
+    } else {
+        "NormalFunction"
+    }
+}
 fn generate_function_wrapper(functions: &Vec<Function>) -> String {
     let mut ret = String::new();
     for func in functions {
         ret.push_str(&format!(
-            "#[no_mangle]\nunsafe fn metacall_register_fn_{}() -> *mut NormalFunction {{\n",
+            "#[no_mangle]\nunsafe fn metacall_register_fn_{}() -> *mut {} {{\n",
+            func.name,
+            function_wrapper_type(func)
+        ));
+        ret.push_str(&format!(
+            "\tlet f = {}::new({});\n",
+            function_wrapper_type(func),
             func.name
         ));
-        ret.push_str(&format!("\tlet f = NormalFunction::new({});\n", func.name));
         ret.push_str("\tBox::into_raw(Box::new(f))\n}\n");
     }
     ret
 }
 
+// async methods would hold the borrow of self across awaits, so they are registered
+// as unsupported, and calling them throws instead
+fn generate_method_wrapper(class_name: &str, method: &Function) -> String {
+    if method.is_async {
+        format!(
+            "\t\t.add_unsupported_method(\"{}\", \"async methods taking self are not supported: {}::{}\")\n",
+            method.name, class_name, method.name
+        )
+    } else {
+        format!(
+            "\t\t.add_method(\"{}\", {}::{})\n",
+            method.name, class_name, method.name
+        )
+    }
+}
+
 fn generate_class_wrapper(classes: &Vec<&crate::Class>) -> String {
     let mut ret = String::new();
     for class in classes {
@@ -45,16 +74,20 @@ fn generate_class_wrapper(classes: &Vec<&crate::Class>) -> String {
         }
         // set methods
         for method in &class.methods {
-            ret.push_str(&format!(
-                "\t\t.add_method(\"{}\", {}::{})\n",
-                method.name, class.name, method.name
-            ));
+            ret.push_str(&generate_method_wrapper(&class.name, method));
         }
         // set static methods
         for method in &class.static_methods {
             ret.push_str(&format!(
-                "\t\t.add_class_method(\"{}\", {}::{})\n",
-                method.name, class.name, method.name
+                "\t\t.{}(\"{}\", {}::{})\n",
+                if method.is_async {
+                    "add_async_class_method"
+                } else {
+                    "add_class_method"
+                },
+                method.name,
+                class.name,
+                method.name
             ));
         }
         // no need to set destructor
@@ -68,11 +101,13 @@ fn generate_function_wrapper_for_package(functions: &Vec<Function>) -> String {
     let mut ret = String::new();
     for func in functions {
         ret.push_str(&format!(
-            "#[no_mangle]\nunsafe fn metacall_register_fn_{}() -> *mut NormalFunction {{\n",
-            func.name
+            "#[no_mangle]\nunsafe fn metacall_register_fn_{}() -> *mut {} {{\n",
+            func.name,
+            function_wrapper_type(func)
         ));
         ret.push_str(&format!(
-            "\tlet f = NormalFunction::new(metacall_package::{});\n",
+            "\tlet f = {}::new(metacall_package::{});\n",
+            function_wrapper_type(func),
             func.name
         ));
         ret.push_str("\tBox::into_raw(Box::new(f))\n}\n");
@@ -109,16 +144,20 @@ fn generate_class_wrapper_for_package(classes: &Vec<&crate::Class>) -> String {
         }
         // set methods
         for method in &class.methods {
-            ret.push_str(&format!(
-                "\t\t.add_method(\"{}\", {}::{})\n",
-                method.name, class.name, method.name
-            ));
+            ret.push_str(&generate_method_wrapper(&class.name, method));
         }
         // set static methods
         for method in &class.static_methods {
             ret.push_str(&format!(
-                "\t\t.add_class_method(\"{}\", {}::{})\n",
-                method.name, class.name, method.name
+                "\t\t.{}(\"{}\", {}::{})\n",
+                if method.is_async {
+                    "add_async_class_method"
+                } else {
+                    "add_class_method"
+                },
+                method.name,
+                class.name,
+                method.name
             ));
         }
         // no need to set destructor
@@ -407,6 +407,21 @@ METACALL_API void *metacall_value_copy(void *v);
 */
 METACALL_API void metacall_value_move(void *src, void *dest);
 
+/**
+*  @brief
+*    Set the allocator of the values created from now on in the current thread,
+*    so they can be freed in bulk along with the allocator (i.e a request pool),
+*    each value is freed with the allocator it was created with, so it must
+*    outlive all of them
+*
+*  @param[in] allocator
+*    Pointer to the allocator of the new values, or null for using the default one
+*
+*  @return
+*    Pointer to the allocator previously set in the current thread
+*/
+METACALL_API void *metacall_value_allocator(void *allocator);
+
 /**
 *  @brief
 *    Convert value @v to boolean
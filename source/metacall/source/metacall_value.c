@@ -228,6 +228,11 @@ void metacall_value_move(void *src, void *dst)
 	value_move(src, dst);
 }
 
+void *metacall_value_allocator(void *allocator)
+{
+	return value_allocator((memory_allocator)allocator);
+}
+
 boolean metacall_value_to_bool(void *v)
 {
 	assert(value_type_id(v) == TYPE_BOOL);
@@ -1,5 +1,12 @@
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    Error, Expr, ExprLit, FnArg, ForeignItem, ForeignItemFn, Ident, ItemForeignMod, Lit, LitStr,
+    MetaNameValue, Pat, ReturnType, Token,
+};
 
 macro_rules! gen_inline_macro {
     ($($name:ident),*) => (
@@ -20,3 +27,244 @@ macro_rules! gen_inline_macro {
 }
 
 gen_inline_macro!(py, node, ts, cs, rb, cob, rpc, java, wasm);
+
+struct IncludeArgs {
+    path: LitStr,
+    handle: bool,
+}
+impl Parse for IncludeArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        let mut handle = false;
+
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let mode: Ident = input.parse()?;
+            if mode != "handle" {
+                return Err(Error::new_spanned(mode, "expected `handle`"));
+            }
+            handle = true;
+
+            input.parse::<Option<Token![,]>>()?;
+        }
+
+        Ok(Self { path, handle })
+    }
+}
+
+macro_rules! gen_include_macro {
+    ($($name:ident => $tag:literal),*) => (
+        $(
+            #[doc = concat!(
+                "Embeds a script at compile time and loads it with the `", $tag, "` loader. ",
+                "Pass `handle` after the path for loading it into its own handle."
+            )]
+            #[proc_macro]
+            pub fn $name(input: TokenStream) -> TokenStream {
+                let IncludeArgs { path, handle } = parse_macro_input!(input as IncludeArgs);
+
+                let result = if handle {
+                    quote! {
+                        ::metacall::loaders::from_memory_handle($tag, ::std::include_str!(#path))
+                    }
+                } else {
+                    quote! {
+                        ::metacall::loaders::from_memory($tag, ::std::include_str!(#path))
+                    }
+                };
+
+                result.into()
+            }
+        )*
+    )
+}
+
+gen_include_macro!(
+    include_py => "py",
+    include_node => "node",
+    include_ts => "ts",
+    include_cs => "cs",
+    include_rb => "rb",
+    include_cob => "cob",
+    include_rpc => "rpc",
+    include_java => "java"
+);
+
+struct BindArgs {
+    tag: LitStr,
+    file: LitStr,
+}
+impl Parse for BindArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut tag = None;
+        let mut file = None;
+
+        for arg in Punctuated::<MetaNameValue, Token![,]>::parse_terminated(input)? {
+            let value = match &arg.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(value),
+                    ..
+                }) => value.clone(),
+                value => return Err(Error::new_spanned(value, "expected a string literal")),
+            };
+
+            if arg.path.is_ident("tag") {
+                tag = Some(value);
+            } else if arg.path.is_ident("file") {
+                file = Some(value);
+            } else {
+                return Err(Error::new_spanned(
+                    arg.path,
+                    "unknown argument, expected `tag` or `file`",
+                ));
+            }
+        }
+
+        match (tag, file) {
+            (Some(tag), Some(file)) => Ok(Self { tag, file }),
+            _ => Err(input.error("expected `tag = \"...\"` and `file = \"...\"` arguments")),
+        }
+    }
+}
+
+fn bind_function(handle: &Ident, function: ForeignItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let ForeignItemFn {
+        attrs, vis, sig, ..
+    } = function;
+
+    if let Some(variadic) = &sig.variadic {
+        return Err(Error::new_spanned(
+            variadic,
+            "variadic functions are not supported",
+        ));
+    }
+
+    let name = &sig.ident;
+    let name_str = name.to_string();
+    let mut params = Vec::new();
+    let mut args = Vec::new();
+    for (index, input) in sig.inputs.iter().enumerate() {
+        let FnArg::Typed(input) = input else {
+            return Err(Error::new_spanned(input, "`self` is not supported"));
+        };
+        let arg = match &*input.pat {
+            Pat::Ident(pat) => pat.ident.clone(),
+            _ => format_ident!("arg{}", index),
+        };
+        let ty = &input.ty;
+
+        params.push(quote! { #arg: #ty });
+        args.push(arg);
+    }
+    let (ret, map_ret) = match &sig.output {
+        ReturnType::Default => (quote! { () }, quote! { ::metacall::MetacallNull }),
+        ReturnType::Type(_, ty) => (quote! { #ty }, quote! { #ty }),
+    };
+    let call = quote! {
+        function
+            .call::<#map_ret>(::metacall::metacall_args![#(#args),*])
+            .map_err(::metacall::MetacallBindError::CallErr)
+    };
+    let call = match &sig.output {
+        ReturnType::Default => quote! { #call.map(|_| ()) },
+        ReturnType::Type(..) => call,
+    };
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis fn #name(#(#params),*) -> ::std::result::Result<#ret, ::metacall::MetacallBindError> {
+            ::std::thread_local! {
+                static FUNCTION: ::std::cell::RefCell<::std::option::Option<::metacall::MetacallFunction>> =
+                    const { ::std::cell::RefCell::new(::std::option::Option::None) };
+            }
+
+            // The cached function is resolved again once it belongs to a destroyed runtime. It's
+            // cloned out of the cache, so the call can reach this function again
+            let function = FUNCTION.with(|function| {
+                let mut function = function.borrow_mut();
+                if let ::std::option::Option::Some(function) =
+                    function.as_ref().filter(|function| function.is_alive())
+                {
+                    return ::std::result::Result::Ok(function.clone());
+                }
+
+                let new_function = #handle(|handle| {
+                    handle
+                        .get_function(#name_str)
+                        .map_err(::metacall::MetacallBindError::CallErr)
+                })?;
+
+                ::std::result::Result::Ok(function.insert(new_function).clone())
+            })?;
+
+            #call
+        }
+    })
+}
+
+/// Generates safe wrappers for the functions of a script. The script is loaded into its own
+/// handle on the first call and the functions are resolved only once, until the runtime is
+/// destroyed. Loading failures aren't cached, so they're retried on the next call. The file is
+/// relative to the directory of the crate's manifest. The handle is shared by all the threads,
+/// while each thread resolves the functions on its own as Metacall values can't be shared between
+/// threads.
+#[proc_macro_attribute]
+pub fn bind(args: TokenStream, input: TokenStream) -> TokenStream {
+    let BindArgs { tag, file } = parse_macro_input!(args as BindArgs);
+    let foreign = parse_macro_input!(input as ItemForeignMod);
+
+    let mut functions = Vec::new();
+    for item in &foreign.items {
+        match item {
+            ForeignItem::Fn(function) => functions.push(function.clone()),
+            item => {
+                return Error::new_spanned(item, "only functions are supported")
+                    .to_compile_error()
+                    .into()
+            }
+        }
+    }
+    let Some(first) = functions.first() else {
+        return TokenStream::new();
+    };
+
+    // Shared by all the functions of the block, named after the first one to avoid collisions
+    let handle = format_ident!("__metacall_bind_handle_{}", first.sig.ident);
+    let functions = match functions
+        .into_iter()
+        .map(|function| bind_function(&handle, function))
+        .collect::<syn::Result<Vec<_>>>()
+    {
+        Ok(functions) => functions,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let result = quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        fn #handle<R>(
+            f: impl FnOnce(&::metacall::MetacallHandle) -> ::std::result::Result<R, ::metacall::MetacallBindError>,
+        ) -> ::std::result::Result<R, ::metacall::MetacallBindError> {
+            static HANDLE: ::std::sync::Mutex<::std::option::Option<::metacall::MetacallBindHandle>> =
+                ::std::sync::Mutex::new(::std::option::Option::None);
+
+            let mut handle = HANDLE
+                .lock()
+                .unwrap_or_else(::std::sync::PoisonError::into_inner);
+            // Only loaded handles are cached, and they're loaded again once the runtime that
+            // loaded them is destroyed
+            if !handle.as_ref().is_some_and(|handle| handle.0.is_alive()) {
+                let path = ::std::path::Path::new(::std::env!("CARGO_MANIFEST_DIR")).join(#file);
+                let new_handle = ::metacall::loaders::from_single_file_handle(#tag, path)
+                    .map_err(::metacall::MetacallBindError::LoadingErr)?;
+
+                *handle = ::std::option::Option::Some(::metacall::MetacallBindHandle(new_handle));
+            }
+
+            f(&handle.as_ref().unwrap().0)
+        }
+
+        #(#functions)*
+    };
+
+    result.into()
+}
@@ -4266,6 +4266,12 @@ extern "C" {
     #[doc = "  @brief\n    Copies the ownership from @src to @dst, including the finalizer,\n    and resets the owner and finalizer of @src\n\n  @param[in] src\n    Source value which will lose the ownership\n\n  @param[in] dst\n    Destination value which will recieve the ownership"]
     pub fn metacall_value_move(src: *mut ::std::os::raw::c_void, dest: *mut ::std::os::raw::c_void);
 }
+extern "C" {
+    #[doc = "  @brief\n    Set the allocator of the values created from now on in the current thread,\n    so they can be freed in bulk along with the allocator (i.e a request pool),\n    each value is freed with the allocator it was created with, so it must\n    outlive all of them\n\n  @param[in] allocator\n    Pointer to the allocator of the new values, or null for using the default one\n\n  @return\n    Pointer to the allocator previously set in the current thread"]
+    pub fn metacall_value_allocator(
+        allocator: *mut ::std::os::raw::c_void,
+    ) -> *mut ::std::os::raw::c_void;
+}
 extern "C" {
     #[doc = "  @brief\n    Convert value @v to boolean\n\n  @param[in] v\n    Reference to the value\n\n  @return\n    Value converted to boolean"]
     pub fn metacall_value_to_bool(v: *mut ::std::os::raw::c_void) -> ::std::os::raw::c_uchar;
@@ -1,5 +1,5 @@
 use crate::types::MetacallValue;
-use std::any::Any;
+use std::{any::Any, ffi::c_void};
 
 pub trait MetacallDowncast: Any {
     fn into_any(self: Box<Self>) -> Box<dyn Any>;
@@ -110,6 +110,11 @@ impl<'c> Clone for Box<dyn MetacallValue + Send + Sync + 'c> {
     }
 }
 
-pub fn metacall_implementer_to_traitobj(v: impl MetacallValue) -> Box<dyn MetacallValue> {
-    Box::new(v) as Box<dyn MetacallValue>
+pub trait MetacallIntoRaw {
+    fn into_metacall_raw_box(self: Box<Self>) -> *mut c_void;
+}
+impl<T: MetacallValue> MetacallIntoRaw for T {
+    fn into_metacall_raw_box(self: Box<Self>) -> *mut c_void {
+        (*self).into_metacall_raw()
+    }
 }
@@ -0,0 +1,272 @@
+use crate::{
+    bindings::{
+        metacall_deserialize, metacall_inspect, metacall_serial, metacall_value_count,
+        metacall_value_destroy, metacall_value_id, metacall_value_to_array, metacall_value_to_bool,
+        metacall_value_to_double, metacall_value_to_int, metacall_value_to_long,
+        metacall_value_to_map, metacall_value_to_short,
+    },
+    check_initialized, parsers,
+    types::{MetacallAllocator, MetacallInspectError},
+};
+use std::{ffi::c_void, ptr, slice};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Represents a type as seen by the loader.
+pub struct MetacallTypeInfo {
+    /// Name of the type in the loaded language. It's empty if the loader doesn't provide one.
+    pub name: String,
+    /// Index of the type in the Metacall protocol, same as the one used by
+    /// [MetacallValue](crate::MetacallValue) implementations.
+    pub id: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Represents a parameter of a function, method or constructor.
+pub struct MetacallParameterInfo {
+    /// Name of the parameter.
+    pub name: String,
+    /// Type of the parameter.
+    pub ty: MetacallTypeInfo,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Represents a function or a method.
+pub struct MetacallFunctionInfo {
+    /// Name of the function.
+    pub name: String,
+    /// Parameters of the function in order.
+    pub parameters: Vec<MetacallParameterInfo>,
+    /// Return type of the function.
+    pub return_type: MetacallTypeInfo,
+    /// Whether the function is async or not.
+    pub is_async: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Represents a class attribute.
+pub struct MetacallAttributeInfo {
+    /// Name of the attribute.
+    pub name: String,
+    /// Type of the attribute.
+    pub ty: MetacallTypeInfo,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Represents a class.
+pub struct MetacallClassInfo {
+    /// Name of the class.
+    pub name: String,
+    /// Parameters of each one of the class constructors.
+    pub constructors: Vec<Vec<MetacallParameterInfo>>,
+    /// Methods of the class.
+    pub methods: Vec<MetacallFunctionInfo>,
+    /// Static methods of the class.
+    pub static_methods: Vec<MetacallFunctionInfo>,
+    /// Attributes of the class.
+    pub attributes: Vec<MetacallAttributeInfo>,
+    /// Static attributes of the class.
+    pub static_attributes: Vec<MetacallAttributeInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Represents a loaded script.
+pub struct MetacallHandleInfo {
+    /// Name of the handle, usually the path of the script.
+    pub name: String,
+    /// Functions exported by the script.
+    pub functions: Vec<MetacallFunctionInfo>,
+    /// Classes exported by the script.
+    pub classes: Vec<MetacallClassInfo>,
+    /// Names of the objects exported by the script.
+    pub objects: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Represents a loader and the scripts loaded by it.
+pub struct MetacallLoaderInfo {
+    /// Tag of the loader, for example `py` or `node`.
+    pub tag: String,
+    /// Scripts loaded by the loader.
+    pub handles: Vec<MetacallHandleInfo>,
+}
+
+fn array_items(v: *mut c_void) -> Vec<*mut c_void> {
+    if v.is_null() || unsafe { metacall_value_id(v) } != 9 {
+        return Vec::new();
+    }
+
+    unsafe { slice::from_raw_parts(metacall_value_to_array(v), metacall_value_count(v)) }.to_vec()
+}
+fn map_entries(v: *mut c_void) -> Vec<(String, *mut c_void)> {
+    if v.is_null() || unsafe { metacall_value_id(v) } != 10 {
+        return Vec::new();
+    }
+
+    unsafe { slice::from_raw_parts(metacall_value_to_map(v), metacall_value_count(v)) }
+        .iter()
+        .filter_map(|pair| {
+            let pair = unsafe { slice::from_raw_parts(metacall_value_to_array(*pair), 2) };
+
+            Some((string(pair[0])?, pair[1]))
+        })
+        .collect()
+}
+fn map_get(v: *mut c_void, key: &str) -> *mut c_void {
+    map_entries(v)
+        .into_iter()
+        .find(|(entry_key, _)| entry_key == key)
+        .map_or(ptr::null_mut(), |(_, value)| value)
+}
+fn string(v: *mut c_void) -> Option<String> {
+    if v.is_null() || unsafe { metacall_value_id(v) } != 7 {
+        return None;
+    }
+
+    parsers::raw_to_metacallobj_leak::<String>(v).ok()
+}
+fn number(v: *mut c_void) -> Option<i64> {
+    if v.is_null() {
+        return None;
+    }
+
+    match unsafe { metacall_value_id(v) } {
+        2 => Some(unsafe { metacall_value_to_short(v) } as i64),
+        3 => Some(unsafe { metacall_value_to_int(v) } as i64),
+        4 => Some(unsafe { metacall_value_to_long(v) }),
+        6 => Some(unsafe { metacall_value_to_double(v) } as i64),
+        _ => None,
+    }
+}
+fn boolean(v: *mut c_void) -> bool {
+    !v.is_null()
+        && unsafe { metacall_value_id(v) } == 0
+        && unsafe { metacall_value_to_bool(v) } != 0
+}
+
+fn parse_type(v: *mut c_void) -> MetacallTypeInfo {
+    let ty = map_get(v, "type");
+
+    MetacallTypeInfo {
+        name: string(map_get(ty, "name")).unwrap_or_default(),
+        id: number(map_get(ty, "id")).unwrap_or_default() as u32,
+    }
+}
+fn parse_parameters(v: *mut c_void) -> Vec<MetacallParameterInfo> {
+    array_items(v)
+        .into_iter()
+        .map(|parameter| MetacallParameterInfo {
+            name: string(map_get(parameter, "name")).unwrap_or_default(),
+            ty: parse_type(parameter),
+        })
+        .collect()
+}
+fn parse_function(v: *mut c_void) -> MetacallFunctionInfo {
+    let signature = map_get(v, "signature");
+
+    MetacallFunctionInfo {
+        name: string(map_get(v, "name")).unwrap_or_default(),
+        parameters: parse_parameters(map_get(signature, "args")),
+        return_type: parse_type(map_get(signature, "ret")),
+        is_async: boolean(map_get(v, "async")),
+    }
+}
+fn parse_functions(v: *mut c_void) -> Vec<MetacallFunctionInfo> {
+    array_items(v).into_iter().map(parse_function).collect()
+}
+fn parse_attributes(v: *mut c_void) -> Vec<MetacallAttributeInfo> {
+    array_items(v)
+        .into_iter()
+        .map(|attribute| MetacallAttributeInfo {
+            name: string(map_get(attribute, "name")).unwrap_or_default(),
+            ty: parse_type(attribute),
+        })
+        .collect()
+}
+fn parse_class(v: *mut c_void) -> MetacallClassInfo {
+    MetacallClassInfo {
+        name: string(map_get(v, "name")).unwrap_or_default(),
+        constructors: array_items(map_get(v, "constructors"))
+            .into_iter()
+            .map(|constructor| parse_parameters(map_get(constructor, "args")))
+            .collect(),
+        methods: parse_functions(map_get(v, "methods")),
+        static_methods: parse_functions(map_get(v, "static_methods")),
+        attributes: parse_attributes(map_get(v, "attributes")),
+        static_attributes: parse_attributes(map_get(v, "static_attributes")),
+    }
+}
+fn parse_handle(v: *mut c_void) -> MetacallHandleInfo {
+    let scope = map_get(v, "scope");
+
+    MetacallHandleInfo {
+        name: string(map_get(v, "name")).unwrap_or_default(),
+        functions: parse_functions(map_get(scope, "funcs")),
+        classes: array_items(map_get(scope, "classes"))
+            .into_iter()
+            .map(parse_class)
+            .collect(),
+        objects: array_items(map_get(scope, "objects"))
+            .into_iter()
+            .filter_map(|object| string(map_get(object, "name")))
+            .collect(),
+    }
+}
+
+/// Inspects all the loaders and the scripts loaded by them. Usage example: ...
+/// ```
+/// metacall::loaders::from_single_file("py", "script.py").unwrap();
+///
+/// for loader in metacall::inspect::loaders().unwrap() {
+///     for handle in loader.handles {
+///         for function in handle.functions {
+///             println!("{}: {}({:?})", loader.tag, function.name, function.parameters);
+///         }
+///     }
+/// }
+/// ```
+pub fn loaders() -> Result<Vec<MetacallLoaderInfo>, MetacallInspectError> {
+    check_initialized!(MetacallInspectError);
+    let allocator = MetacallAllocator::std();
+    let mut size = 0;
+    let buffer = unsafe { metacall_inspect(&mut size, allocator.as_ptr()) };
+
+    if buffer.is_null() {
+        return Err(MetacallInspectError::InspectFailure);
+    }
+
+    let inspect =
+        unsafe { metacall_deserialize(metacall_serial(), buffer, size, allocator.as_ptr()) };
+    unsafe { allocator.free(buffer.cast()) };
+
+    if inspect.is_null() {
+        return Err(MetacallInspectError::DeserializeFailure);
+    }
+
+    let loaders = map_entries(inspect)
+        .into_iter()
+        .map(|(tag, handles)| MetacallLoaderInfo {
+            tag,
+            handles: array_items(handles).into_iter().map(parse_handle).collect(),
+        })
+        .collect();
+
+    unsafe { metacall_value_destroy(inspect) };
+
+    Ok(loaders)
+}
+
+/// Finds a function by its name between all the loaded scripts. Usage example: ...
+/// ```
+/// let sum = metacall::inspect::function("sum").unwrap().unwrap();
+///
+/// println!("sum has {} parameters", sum.parameters.len());
+/// ```
+pub fn function(name: impl ToString) -> Result<Option<MetacallFunctionInfo>, MetacallInspectError> {
+    let name = name.to_string();
+
+    Ok(loaders()?
+        .into_iter()
+        .flat_map(|loader| loader.handles)
+        .flat_map(|handle| handle.functions)
+        .find(|function| function.name == name))
+}
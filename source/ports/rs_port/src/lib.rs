@@ -63,7 +63,7 @@ pub(crate) mod helpers;
 pub(crate) mod parsers;
 pub(crate) use macros::private_macros::*;
 
-/// Contains Metacall loaders from file and memory. Usage example: ...
+/// Contains Metacall loaders from file, memory, package and configuration. Usage example: ...
 /// ```
 /// // Loading a single file with Nodejs.
 /// metacall::loaders::from_single_file("node", "index.js").unwrap();
@@ -74,11 +74,54 @@ pub(crate) use macros::private_macros::*;
 /// // Loading a string with Nodejs.
 /// let script = "function greet() { return 'hi there!' }; module.exports = { greet };";
 /// metacall::loaders::from_memory("node", script).unwrap();
+///
+/// // Loading a file with Nodejs into its own handle instead of the global scope.
+/// let handle = metacall::loaders::from_single_file_handle("node", "index.js").unwrap();
+///
+/// // Loading a prebuilt .NET assembly.
+/// metacall::loaders::from_package("cs", "library.dll").unwrap();
+///
+/// // Loading the scripts described by a configuration file.
+/// metacall::loaders::from_configuration("metacall.json").unwrap();
 /// ```
 pub mod loaders;
 
+/// Contains Metacall introspection of the loaded scripts. Usage example: ...
+/// ```
+/// // Listing the functions of every loaded script.
+/// for loader in metacall::inspect::loaders().unwrap() {
+///     for handle in loader.handles {
+///         println!("{} ({}): {:#?}", handle.name, loader.tag, handle.functions);
+///     }
+/// }
+///
+/// // Finding a function by its name.
+/// let sum = metacall::inspect::function("sum").unwrap();
+/// ```
+pub mod inspect;
+
+/// Contains the serialization of Metacall values through the serializers of the core. Usage
+/// example: ...
+/// ```
+/// // Logging the arguments of a call.
+/// let args = vec![1, 2];
+/// println!("sum({})", metacall::serial::to_json(args.clone()).unwrap());
+/// let sum = metacall::metacall::<i32>("sum", args).unwrap();
+///
+/// // Reading a value sent by another process.
+/// let numbers = metacall::serial::from_json::<Vec<i32>>("[1, 2, 3]").unwrap();
+/// ```
+pub mod serial;
+
+/// Contains the serde integration for converting Rust types to Metacall values and vice versa.
+/// Requires the `serde` feature. Checkout [metacall_serde](crate::metacall_serde) for usage.
+#[cfg(feature = "serde")]
+pub mod serde;
+
 mod types;
-pub use switch::initialize;
+#[cfg(unix)]
+pub use switch::fork;
+pub use switch::{initialize, MetacallBuilder, MetacallLog};
 
 #[doc(hidden)]
 pub mod macros;
@@ -107,11 +150,32 @@ pub use metacall_mod::*;
 /// ts! {
 ///     console.log("hello world");
 /// }
+///
+/// // Embedding a Python file at compile time, the path is relative to the current file
+/// include_py!("script.py").unwrap();
+///
+/// // Same but loading it into its own handle
+/// let handle = include_py!("script.py", handle).unwrap();
+/// let sum = handle.call::<i64>("sum", [1, 2]).unwrap();
 /// ```
 pub mod inline {
     pub use metacall_inline::*;
 }
 
+/// Generates safe wrappers for the functions of a script. Arguments and return values are
+/// converted with [MetacallValue](MetacallValue). Usage example: ...
+/// ```
+/// #[metacall::bind(tag = "py", file = "math.py")]
+/// extern "C" {
+///     fn sum(a: f64, b: f64) -> f64;
+///     fn log(message: String);
+/// }
+///
+/// let result = sum(1.0, 2.0).unwrap();
+/// log(result.to_string()).unwrap();
+/// ```
+pub use metacall_inline::bind;
+
 #[allow(warnings)]
 #[doc(hidden)]
 pub mod bindings;
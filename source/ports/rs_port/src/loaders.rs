@@ -1,10 +1,13 @@
 use crate::{
-    bindings::{metacall_load_from_file, metacall_load_from_memory},
-    cstring_enum,
-    types::MetacallLoaderError,
+    bindings::{
+        metacall_load_from_configuration, metacall_load_from_file, metacall_load_from_memory,
+        metacall_load_from_package,
+    },
+    check_initialized, cstring_enum,
+    types::{MetacallAllocator, MetacallHandle, MetacallLoaderError},
 };
 use std::{
-    ffi::CString,
+    ffi::{c_void, CString},
     path::{Path, PathBuf},
     ptr,
 };
@@ -29,6 +32,46 @@ pub fn from_file(
     tag: impl ToString,
     scripts: impl IntoIterator<Item = impl AsRef<Path>>,
 ) -> Result<(), MetacallLoaderError> {
+    from_file_inner(tag, scripts, ptr::null_mut())
+}
+/// Loads a script from a single file into its own [handle](MetacallHandle) instead of the global
+/// scope. Usage example: ...
+/// ```
+/// // A Nodejs script
+/// let handle = metacall::loaders::from_single_file_handle("node", "index.js").unwrap();
+/// ```
+pub fn from_single_file_handle(
+    tag: impl ToString,
+    script: impl AsRef<Path>,
+) -> Result<MetacallHandle, MetacallLoaderError> {
+    from_file_handle(tag, [script])
+}
+/// Loads a script from file into its own [handle](MetacallHandle) instead of the global scope.
+/// The scripts are unloaded when the handle gets dropped. Usage example: ...
+/// ```
+/// // Two versions of the same Nodejs module side by side
+/// let v1 = metacall::loaders::from_file_handle("node", ["v1/index.js"]).unwrap();
+/// let v2 = metacall::loaders::from_file_handle("node", ["v2/index.js"]).unwrap();
+///
+/// let greet_v1 = v1.call_no_arg::<String>("greet").unwrap();
+/// let greet_v2 = v2.call_no_arg::<String>("greet").unwrap();
+/// ```
+pub fn from_file_handle(
+    tag: impl ToString,
+    scripts: impl IntoIterator<Item = impl AsRef<Path>>,
+) -> Result<MetacallHandle, MetacallLoaderError> {
+    let mut handle: *mut c_void = ptr::null_mut();
+
+    from_file_inner(tag, scripts, &mut handle)?;
+
+    Ok(MetacallHandle::new_raw(handle))
+}
+fn from_file_inner(
+    tag: impl ToString,
+    scripts: impl IntoIterator<Item = impl AsRef<Path>>,
+    handle: *mut *mut c_void,
+) -> Result<(), MetacallLoaderError> {
+    check_initialized!(MetacallLoaderError);
     let c_tag = cstring_enum!(tag, MetacallLoaderError)?;
     let mut c_script: CString;
 
@@ -56,7 +99,7 @@ pub fn from_file(
             c_tag.as_ptr(),
             new_scripts.as_mut_ptr(),
             new_scripts.len(),
-            ptr::null_mut(),
+            handle,
         )
     } != 0
     {
@@ -74,21 +117,143 @@ pub fn from_file(
 /// metacall::loaders::from_memory("node", script).unwrap();
 /// ```
 pub fn from_memory(tag: impl ToString, script: impl ToString) -> Result<(), MetacallLoaderError> {
+    from_memory_inner(tag, script, ptr::null_mut())
+}
+/// Loads a script from memory into its own [handle](MetacallHandle) instead of the global scope.
+/// The script is unloaded when the handle gets dropped. Usage example: ...
+/// ```
+/// let script = "function greet() { return 'hi there!' }; module.exports = { greet };";
+///
+/// // A Nodejs script
+/// let handle = metacall::loaders::from_memory_handle("node", script).unwrap();
+/// let greet = handle.call_no_arg::<String>("greet").unwrap();
+/// ```
+pub fn from_memory_handle(
+    tag: impl ToString,
+    script: impl ToString,
+) -> Result<MetacallHandle, MetacallLoaderError> {
+    let mut handle: *mut c_void = ptr::null_mut();
+
+    from_memory_inner(tag, script, &mut handle)?;
+
+    Ok(MetacallHandle::new_raw(handle))
+}
+fn from_memory_inner(
+    tag: impl ToString,
+    script: impl ToString,
+    handle: *mut *mut c_void,
+) -> Result<(), MetacallLoaderError> {
+    check_initialized!(MetacallLoaderError);
     let script = script.to_string();
     let c_tag = cstring_enum!(tag, MetacallLoaderError)?;
     let c_script = cstring_enum!(script, MetacallLoaderError)?;
 
-    if unsafe {
-        metacall_load_from_memory(
-            c_tag.as_ptr(),
-            c_script.as_ptr(),
-            script.len(),
-            ptr::null_mut(),
-        )
-    } != 0
+    if unsafe { metacall_load_from_memory(c_tag.as_ptr(), c_script.as_ptr(), script.len(), handle) }
+        != 0
     {
         return Err(MetacallLoaderError::FromMemoryFailure);
     }
 
     Ok(())
 }
+
+/// Loads a package, such as a compiled library, a .NET assembly or a Rust rlib, so prebuilt
+/// artifacts can be shipped instead of their sources. The path is resolved by the loader, so it
+/// may be relative to its execution paths. Usage example: ...
+/// ```
+/// // A .NET assembly
+/// metacall::loaders::from_package("cs", "library.dll").unwrap();
+///
+/// // A Rust library
+/// metacall::loaders::from_package("rs", "libmy_library.rlib").unwrap();
+/// ```
+pub fn from_package(tag: impl ToString, path: impl AsRef<Path>) -> Result<(), MetacallLoaderError> {
+    from_package_inner(tag, path, ptr::null_mut())
+}
+/// Loads a package into its own [handle](MetacallHandle) instead of the global scope. The package
+/// is unloaded when the handle gets dropped. Usage example: ...
+/// ```
+/// let handle = metacall::loaders::from_package_handle("cs", "library.dll").unwrap();
+/// ```
+pub fn from_package_handle(
+    tag: impl ToString,
+    path: impl AsRef<Path>,
+) -> Result<MetacallHandle, MetacallLoaderError> {
+    let mut handle: *mut c_void = ptr::null_mut();
+
+    from_package_inner(tag, path, &mut handle)?;
+
+    Ok(MetacallHandle::new_raw(handle))
+}
+fn from_package_inner(
+    tag: impl ToString,
+    path: impl AsRef<Path>,
+    handle: *mut *mut c_void,
+) -> Result<(), MetacallLoaderError> {
+    check_initialized!(MetacallLoaderError);
+    let c_tag = cstring_enum!(tag, MetacallLoaderError)?;
+    let c_path = cstring_enum!(path.as_ref().display(), MetacallLoaderError)?;
+
+    if unsafe { metacall_load_from_package(c_tag.as_ptr(), c_path.as_ptr(), handle) } != 0 {
+        return Err(MetacallLoaderError::FromPackageFailure);
+    }
+
+    Ok(())
+}
+
+/// Loads the scripts described by a configuration file. The loader is taken from its
+/// `language_id` field and the scripts from its `scripts` field, which are relative to its
+/// `path` field if there's one. A relative `path` is resolved from the configuration file
+/// directory. Usage example: ...
+/// ```
+/// // metacall.json:
+/// // {
+/// //     "language_id": "node",
+/// //     "path": ".",
+/// //     "scripts": ["index.js", "main.js"]
+/// // }
+/// metacall::loaders::from_configuration("metacall.json").unwrap();
+/// ```
+pub fn from_configuration(path: impl AsRef<Path>) -> Result<(), MetacallLoaderError> {
+    from_configuration_inner(path, ptr::null_mut())
+}
+/// Loads the scripts described by a configuration file into their own [handle](MetacallHandle)
+/// instead of the global scope. The scripts are unloaded when the handle gets dropped.
+/// Usage example: ...
+/// ```
+/// let handle = metacall::loaders::from_configuration_handle("metacall.json").unwrap();
+/// ```
+pub fn from_configuration_handle(
+    path: impl AsRef<Path>,
+) -> Result<MetacallHandle, MetacallLoaderError> {
+    let mut handle: *mut c_void = ptr::null_mut();
+
+    from_configuration_inner(path, &mut handle)?;
+
+    Ok(MetacallHandle::new_raw(handle))
+}
+fn from_configuration_inner(
+    path: impl AsRef<Path>,
+    handle: *mut *mut c_void,
+) -> Result<(), MetacallLoaderError> {
+    check_initialized!(MetacallLoaderError);
+    let path = PathBuf::from(path.as_ref());
+
+    if !path.exists() {
+        return Err(MetacallLoaderError::FileNotFound(path));
+    }
+    if !path.is_file() {
+        return Err(MetacallLoaderError::NotAFileOrPermissionDenied(path));
+    }
+
+    let c_path = cstring_enum!(path.display(), MetacallLoaderError)?;
+    // The configuration is parsed with the allocator and freed before the call returns
+    let allocator = MetacallAllocator::std();
+
+    if unsafe { metacall_load_from_configuration(c_path.as_ptr(), handle, allocator.as_ptr()) } != 0
+    {
+        return Err(MetacallLoaderError::FromConfigurationFailure);
+    }
+
+    Ok(())
+}
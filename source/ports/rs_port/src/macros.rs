@@ -35,6 +35,21 @@ pub(crate) mod private_macros {
         }};
     }
 
+    macro_rules! check_initialized {
+        ($enum:ident) => {
+            if !$crate::switch::is_initialized() {
+                return Err($enum::NotInitialized);
+            }
+        };
+
+        ($generation:expr, $enum:ident) => {
+            if !$generation.is_alive() {
+                return Err($enum::NotInitialized);
+            }
+        };
+    }
+
+    pub(crate) use check_initialized;
     pub(crate) use cstring;
     pub(crate) use cstring_enum;
 }
@@ -126,3 +141,18 @@ macro_rules! match_metacall_value_mut {
         }
     )
 }
+#[macro_export]
+/// Creates a list of arguments of different types, which is a vector of
+/// [MetacallValue](MetacallValue) trait objects. For example: ...
+/// ```
+/// use metacall::{metacall, metacall_args};
+///
+/// let ret = metacall::<String>("format", metacall_args![1, String::from("a"), true]).unwrap();
+/// ```
+macro_rules! metacall_args {
+    ($($arg:expr),* $(,)?) => {
+        <::std::vec::Vec<::std::boxed::Box<dyn $crate::MetacallValue>>>::from([
+            $(::std::boxed::Box::new($arg) as ::std::boxed::Box<dyn $crate::MetacallValue>),*
+        ])
+    };
+}
@@ -1,9 +1,12 @@
 use crate::{
-    bindings::{metacall_function, metacall_value_destroy, metacallfv_s},
-    cstring_enum, parsers,
-    types::{MetacallError, MetacallNull, MetacallValue},
+    bindings::{metacall_await_s, metacall_function, metacall_value_destroy, metacallfv_s},
+    check_initialized, cstring_enum, parsers,
+    types::{
+        MetacallArgs, MetacallError, MetacallFunction, MetacallFuture, MetacallRegisterError,
+        MetacallValue,
+    },
 };
-use std::ffi::c_void;
+use std::{ffi::c_void, future::Future};
 
 // Used for documentation.
 #[allow(unused_imports)]
@@ -11,8 +14,9 @@ use crate::match_metacall_value;
 
 fn metacall_inner(
     func: impl ToString,
-    args: impl IntoIterator<Item = impl MetacallValue>,
+    args: impl MetacallArgs,
 ) -> Result<*mut c_void, MetacallError> {
+    check_initialized!(MetacallError);
     let c_function = cstring_enum!(func, MetacallError)?;
     let c_func = unsafe { metacall_function(c_function.as_ptr()) };
 
@@ -20,7 +24,8 @@ fn metacall_inner(
         return Err(MetacallError::FunctionNotFound);
     }
 
-    let mut c_args = parsers::metacallobj_to_raw_args(args);
+    let mut c_args =
+        parsers::metacallobj_to_raw_args(args).map_err(MetacallError::FailedConversion)?;
     let args_length = c_args.len();
 
     let ret = unsafe { metacallfv_s(c_func, c_args.as_mut_ptr(), args_length) };
@@ -34,13 +39,15 @@ fn metacall_inner(
 /// Calls a function same as [metacall](metacall) but returns a trait object
 /// of [MetacallValue](MetacallValue). This is useful when you don't know the return
 /// type of that function or the function may return multiple types. Checkout
-/// [match_metacall_value](match_metacall_value) for unwrapping the inner value. For example: ...
+/// [match_metacall_value](match_metacall_value) for unwrapping the inner value, or convert it into
+/// [MetacallAny](crate::MetacallAny) to match it natively. For example: ...
 /// ```
 /// let sum = metacall::metacall_untyped("sum", [1, 2]).unwrap();
+/// let sum: metacall::MetacallAny = sum.into();
 /// ```
 pub fn metacall_untyped(
     func: impl ToString,
-    args: impl IntoIterator<Item = impl MetacallValue>,
+    args: impl MetacallArgs,
 ) -> Result<Box<dyn MetacallValue>, MetacallError> {
     Ok(parsers::raw_to_metacallobj_untyped(metacall_inner(
         func, args,
@@ -53,7 +60,7 @@ pub fn metacall_untyped(
 pub fn metacall_untyped_no_arg(
     func: impl ToString,
 ) -> Result<Box<dyn MetacallValue>, MetacallError> {
-    metacall_untyped(func, [] as [MetacallNull; 0])
+    metacall_untyped(func, ())
 }
 /// Calls a function with arguments. The generic parameter is the return type of the function
 /// you're calling. Checkout [MetacallValue](MetacallValue) for possible types.
@@ -63,11 +70,11 @@ pub fn metacall_untyped_no_arg(
 /// ```
 pub fn metacall<T: MetacallValue>(
     func: impl ToString,
-    args: impl IntoIterator<Item = impl MetacallValue>,
+    args: impl MetacallArgs,
 ) -> Result<T, MetacallError> {
     match parsers::raw_to_metacallobj::<T>(metacall_inner(func, args)?) {
         Ok(ret) => Ok(ret),
-        Err(original) => Err(MetacallError::FailedCasting(original)),
+        Err(original) => Err(MetacallError::from_failed_casting(original)),
     }
 }
 /// Calls a function same as [metacall](metacall) without passing any arguments. For example: ...
@@ -75,5 +82,92 @@ pub fn metacall<T: MetacallValue>(
 /// let greet = metacall::metacall_no_arg::<String>("greet").unwrap();
 /// ```
 pub fn metacall_no_arg<T: MetacallValue>(func: impl ToString) -> Result<T, MetacallError> {
-    metacall::<T>(func, [] as [MetacallNull; 0])
+    metacall::<T>(func, ())
+}
+fn metacall_async_inner(
+    func: impl ToString,
+    args: impl MetacallArgs,
+) -> Result<MetacallFuture, MetacallError> {
+    check_initialized!(MetacallError);
+    let c_function = cstring_enum!(func, MetacallError)?;
+    // Check the function first as awaiting a missing one doesn't tell us anything
+    if unsafe { metacall_function(c_function.as_ptr()) }.is_null() {
+        return Err(MetacallError::FunctionNotFound);
+    }
+
+    let mut c_args =
+        parsers::metacallobj_to_raw_args(args).map_err(MetacallError::FailedConversion)?;
+    let future = MetacallFuture::from_await(|resolve, reject, data| unsafe {
+        metacall_await_s(
+            c_function.as_ptr(),
+            c_args.as_mut_ptr(),
+            c_args.len(),
+            resolve,
+            reject,
+            data,
+        )
+    });
+
+    for c_arg in c_args {
+        unsafe { metacall_value_destroy(c_arg) };
+    }
+
+    future.ok_or(MetacallError::AwaitFailure)
+}
+/// Calls an asynchronous function with arguments and returns a [Future](std::future::Future)
+/// that resolves when the function does, so it can be awaited inside any async runtime. The
+/// function is called right away. A rejection is given in the `Thrown` error variant if it's an
+/// exception, or in the `Rejected` one otherwise. The future can't be sent between threads, checkout
+/// [MetacallClient::call_async](crate::MetacallClient#method.call_async) for that. For example: ...
+/// ```
+/// async fn handler() {
+///     let user = metacall::metacall_async::<String>("fetch_user", [1]).await.unwrap();
+/// }
+/// ```
+pub fn metacall_async<T: MetacallValue>(
+    func: impl ToString,
+    args: impl MetacallArgs,
+) -> impl Future<Output = Result<T, MetacallError>> {
+    MetacallFuture::await_typed(metacall_async_inner(func, args))
+}
+/// Calls an asynchronous function same as [metacall_async](metacall_async) without passing any
+/// arguments. For example: ...
+/// ```
+/// async fn handler() {
+///     let users = metacall::metacall_async_no_arg::<Vec<String>>("fetch_users").await.unwrap();
+/// }
+/// ```
+pub fn metacall_async_no_arg<T: MetacallValue>(
+    func: impl ToString,
+) -> impl Future<Output = Result<T, MetacallError>> {
+    metacall_async::<T>(func, ())
+}
+/// Registers a Rust closure as a function in the global scope, so the loaded scripts can call
+/// it by its name. Arguments are received as they are passed by the caller and the returned
+/// value is handed back to it. For example: ...
+/// ```
+/// use metacall::{match_metacall_value_ref, MetacallNull};
+///
+/// metacall::register("log", |args| {
+///     for arg in args {
+///         match_metacall_value_ref!(arg, {
+///             str: String => println!("{}", str),
+///             _ => println!("{:#?}", arg)
+///         });
+///     }
+///
+///     MetacallNull()
+/// })
+/// .unwrap();
+/// ```
+pub fn register<R: MetacallValue>(
+    name: impl ToString,
+    closure: impl Fn(Vec<Box<dyn MetacallValue>>) -> R + Send + Sync + 'static,
+) -> Result<(), MetacallRegisterError> {
+    let c_name = cstring_enum!(name, MetacallRegisterError)?;
+
+    // The function is kept alive by the global scope
+    MetacallFunction::register_inner(Some(&c_name), closure)?;
+
+    Ok(())
 }
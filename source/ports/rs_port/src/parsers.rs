@@ -1,11 +1,18 @@
 use crate::{
-    bindings::metacall_value_id,
+    bindings::{metacall_value_destroy, metacall_value_id},
     types::{
-        MetacallClass, MetacallException, MetacallFunction, MetacallFuture, MetacallNull,
+        MetacallAny, MetacallArgs, MetacallBuffer, MetacallClass, MetacallConversionError,
+        MetacallException, MetacallFunction, MetacallFuture, MetacallMap, MetacallNull,
         MetacallObject, MetacallPointer, MetacallThrowable, MetacallValue,
     },
 };
-use std::{collections::HashMap, ffi::c_void};
+use std::{cell::RefCell, ffi::c_void, ptr};
+
+thread_local! {
+    // First failure of the conversion in progress, values can't return it as they are converted
+    // with `into_metacall_raw`, which may be nested inside arrays and maps.
+    static CONVERSION_ERROR: RefCell<Option<MetacallConversionError>> = const { RefCell::new(None) };
+}
 
 fn metacallobj_result_wrap<T: MetacallValue>(
     v: Result<T, Box<dyn MetacallValue>>,
@@ -27,12 +34,21 @@ pub fn raw_to_metacallobj<T: MetacallValue>(ret: *mut c_void) -> Result<T, Box<d
         }
     }
 
-    if unsafe { metacall_value_id(ret) } == T::get_metacall_id() {
+    if T::accepts_metacall_id(unsafe { metacall_value_id(ret) }) {
         <T>::from_metacall_raw(ret)
     } else {
         Err(raw_to_metacallobj_untyped(ret))
     }
 }
+pub fn metacallobj_untyped_to_typed<T: MetacallValue>(
+    value: Box<dyn MetacallValue>,
+) -> Result<T, Box<dyn MetacallValue>> {
+    match value.downcast::<T>() {
+        Ok(value) => Ok(value),
+        // Goes through Metacall, so the value can be casted the same way as a returned one
+        Err(original) => raw_to_metacallobj::<T>(original.into_metacall_raw_box()),
+    }
+}
 pub fn raw_to_metacallobj_leak<T: MetacallValue>(
     ret: *mut c_void,
 ) -> Result<T, Box<dyn MetacallValue>> {
@@ -46,7 +62,7 @@ pub fn raw_to_metacallobj_leak<T: MetacallValue>(
         }
     }
 
-    if unsafe { metacall_value_id(ret) } == T::get_metacall_id() {
+    if T::accepts_metacall_id(unsafe { metacall_value_id(ret) }) {
         <T>::from_metacall_raw_leak(ret)
     } else {
         Err(raw_to_metacallobj_untyped_leak(ret))
@@ -64,9 +80,11 @@ pub fn raw_to_metacallobj_untyped(ret: *mut c_void) -> Box<dyn MetacallValue> {
         (_, 5) => metacallobj_result_wrap(f32::from_metacall_raw(ret)),
         (_, 6) => metacallobj_result_wrap(f64::from_metacall_raw(ret)),
         (_, 7) => metacallobj_result_wrap(String::from_metacall_raw(ret)),
-        (_, 8) => metacallobj_result_wrap(<Vec<i8>>::from_metacall_raw(ret)),
-        (_, 9) => metacallobj_result_wrap(<Vec<MetacallNull>>::from_metacall_raw(ret)),
-        (_, 10) => metacallobj_result_wrap(<HashMap<String, MetacallNull>>::from_metacall_raw(ret)),
+        (_, 8) => metacallobj_result_wrap(MetacallBuffer::from_metacall_raw(ret)),
+        (_, 9) => metacallobj_result_wrap(<Vec<MetacallAny>>::from_metacall_raw(ret)),
+        (_, 10) => metacallobj_result_wrap(
+            <MetacallMap<MetacallAny, MetacallAny>>::from_metacall_raw(ret),
+        ),
         (_, 11) => metacallobj_result_wrap(<MetacallPointer>::from_metacall_raw(ret)),
         (_, 12) => metacallobj_result_wrap(MetacallFuture::from_metacall_raw(ret)),
         (_, 13) => metacallobj_result_wrap(MetacallFunction::from_metacall_raw(ret)),
@@ -89,11 +107,11 @@ pub fn raw_to_metacallobj_untyped_leak(ret: *mut c_void) -> Box<dyn MetacallValu
         (_, 5) => metacallobj_result_wrap(f32::from_metacall_raw_leak(ret)),
         (_, 6) => metacallobj_result_wrap(f64::from_metacall_raw_leak(ret)),
         (_, 7) => metacallobj_result_wrap(String::from_metacall_raw_leak(ret)),
-        (_, 8) => metacallobj_result_wrap(<Vec<i8>>::from_metacall_raw_leak(ret)),
-        (_, 9) => metacallobj_result_wrap(<Vec<MetacallNull>>::from_metacall_raw_leak(ret)),
-        (_, 10) => {
-            metacallobj_result_wrap(<HashMap<String, MetacallNull>>::from_metacall_raw_leak(ret))
-        }
+        (_, 8) => metacallobj_result_wrap(MetacallBuffer::from_metacall_raw_leak(ret)),
+        (_, 9) => metacallobj_result_wrap(<Vec<MetacallAny>>::from_metacall_raw_leak(ret)),
+        (_, 10) => metacallobj_result_wrap(
+            <MetacallMap<MetacallAny, MetacallAny>>::from_metacall_raw_leak(ret),
+        ),
         (_, 11) => metacallobj_result_wrap(<MetacallPointer>::from_metacall_raw_leak(ret)),
         (_, 12) => metacallobj_result_wrap(MetacallFuture::from_metacall_raw_leak(ret)),
         (_, 13) => metacallobj_result_wrap(MetacallFunction::from_metacall_raw_leak(ret)),
@@ -106,13 +124,46 @@ pub fn raw_to_metacallobj_untyped_leak(ret: *mut c_void) -> Box<dyn MetacallValu
     }
 }
 
-pub fn metacallobj_to_raw(arg: impl MetacallValue) -> *mut c_void {
-    arg.into_metacall_raw()
+// Records the failure of a conversion, the value it returns must be given in place of the
+// converted one.
+pub fn conversion_failure(error: MetacallConversionError) -> *mut c_void {
+    CONVERSION_ERROR.with(|slot| {
+        slot.borrow_mut().get_or_insert(error);
+    });
+
+    ptr::null_mut()
+}
+fn take_conversion_failure() -> Option<MetacallConversionError> {
+    CONVERSION_ERROR.with(|slot| slot.borrow_mut().take())
+}
+
+pub fn metacallobj_to_raw(arg: impl MetacallValue) -> Result<*mut c_void, MetacallConversionError> {
+    take_conversion_failure();
+    let c_arg = arg.into_metacall_raw();
+
+    match take_conversion_failure() {
+        None => Ok(c_arg),
+        Some(error) => {
+            unsafe { metacall_value_destroy(c_arg) };
+
+            Err(error)
+        }
+    }
 }
 pub fn metacallobj_to_raw_args(
-    args: impl IntoIterator<Item = impl MetacallValue>,
-) -> Vec<*mut c_void> {
-    args.into_iter()
-        .map(|arg| arg.into_metacall_raw())
-        .collect::<Vec<*mut c_void>>()
+    args: impl MetacallArgs,
+) -> Result<Vec<*mut c_void>, MetacallConversionError> {
+    take_conversion_failure();
+    let c_args = args.into_metacall_raw_args();
+
+    match take_conversion_failure() {
+        None => Ok(c_args),
+        Some(error) => {
+            for c_arg in c_args {
+                unsafe { metacall_value_destroy(c_arg) };
+            }
+
+            Err(error)
+        }
+    }
 }
@@ -0,0 +1,740 @@
+use crate::{
+    bindings::*,
+    cstring, parsers,
+    types::{MetacallConversionError, MetacallSerdeError, MetacallValue},
+};
+use ::serde::{
+    de::{
+        self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess,
+        SeqAccess, VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any,
+    ser::{
+        self, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+        SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+    },
+};
+use std::{
+    ffi::{c_void, CStr},
+    mem, slice,
+};
+
+fn create_string(v: &str) -> Result<*mut c_void, MetacallSerdeError> {
+    let c_str = cstring!(v).map_err(|err| MetacallSerdeError::new(err.nul_error))?;
+
+    Ok(unsafe { metacall_value_create_string(c_str.as_ptr(), v.len()) })
+}
+fn create_array(values: Vec<*mut c_void>) -> *mut c_void {
+    let mut values = values
+        .into_iter()
+        .map(|value| value.cast_const())
+        .collect::<Vec<*const c_void>>();
+
+    unsafe { metacall_value_create_array(values.as_mut_ptr(), values.len()) }
+}
+fn create_map(pairs: Vec<*mut c_void>) -> *mut c_void {
+    let mut pairs = pairs
+        .into_iter()
+        .map(|pair| pair.cast_const())
+        .collect::<Vec<*const c_void>>();
+
+    unsafe { metacall_value_create_map(pairs.as_mut_ptr(), pairs.len()) }
+}
+// Enum variants with data are represented as a map with a single key, same as JSON.
+fn create_variant(variant: &str, value: *mut c_void) -> Result<*mut c_void, MetacallSerdeError> {
+    let key = match create_string(variant) {
+        Ok(key) => key,
+        Err(err) => {
+            unsafe { metacall_value_destroy(value) };
+
+            return Err(err);
+        }
+    };
+
+    Ok(create_map(vec![create_array(vec![key, value])]))
+}
+fn destroy_all(values: Vec<*mut c_void>) {
+    for value in values {
+        unsafe { metacall_value_destroy(value) };
+    }
+}
+
+/// Serializer that converts any [Serialize](::serde::Serialize) type into a Metacall value.
+/// Structs and maps become Metacall maps, sequences and tuples become arrays and enum variants
+/// with data become a map with the variant name as the only key.
+pub struct MetacallSerializer;
+
+#[doc(hidden)]
+pub struct MetacallSerializeArray {
+    values: Vec<*mut c_void>,
+    variant: Option<&'static str>,
+}
+impl MetacallSerializeArray {
+    fn push<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), MetacallSerdeError> {
+        self.values.push(value.serialize(MetacallSerializer)?);
+
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<*mut c_void, MetacallSerdeError> {
+        let array = create_array(mem::take(&mut self.values));
+
+        match self.variant {
+            Some(variant) => create_variant(variant, array),
+            None => Ok(array),
+        }
+    }
+}
+impl Drop for MetacallSerializeArray {
+    fn drop(&mut self) {
+        destroy_all(mem::take(&mut self.values));
+    }
+}
+
+#[doc(hidden)]
+pub struct MetacallSerializeMap {
+    key: Option<*mut c_void>,
+    pairs: Vec<*mut c_void>,
+    variant: Option<&'static str>,
+}
+impl MetacallSerializeMap {
+    fn push_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), MetacallSerdeError> {
+        self.key = Some(key.serialize(MetacallSerializer)?);
+
+        Ok(())
+    }
+
+    fn push_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), MetacallSerdeError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| MetacallSerdeError::new("Map value serialized before its key!"))?;
+        let value = match value.serialize(MetacallSerializer) {
+            Ok(value) => value,
+            Err(err) => {
+                unsafe { metacall_value_destroy(key) };
+
+                return Err(err);
+            }
+        };
+
+        self.pairs.push(create_array(vec![key, value]));
+
+        Ok(())
+    }
+
+    fn push_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), MetacallSerdeError> {
+        self.key = Some(create_string(key)?);
+
+        self.push_value(value)
+    }
+
+    fn finish(mut self) -> Result<*mut c_void, MetacallSerdeError> {
+        let map = create_map(mem::take(&mut self.pairs));
+
+        match self.variant {
+            Some(variant) => create_variant(variant, map),
+            None => Ok(map),
+        }
+    }
+}
+impl Drop for MetacallSerializeMap {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            unsafe { metacall_value_destroy(key) };
+        }
+
+        destroy_all(mem::take(&mut self.pairs));
+    }
+}
+
+impl ser::Serializer for MetacallSerializer {
+    type Ok = *mut c_void;
+    type Error = MetacallSerdeError;
+
+    type SerializeSeq = MetacallSerializeArray;
+    type SerializeTuple = MetacallSerializeArray;
+    type SerializeTupleStruct = MetacallSerializeArray;
+    type SerializeTupleVariant = MetacallSerializeArray;
+    type SerializeMap = MetacallSerializeMap;
+    type SerializeStruct = MetacallSerializeMap;
+    type SerializeStructVariant = MetacallSerializeMap;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(v.into_metacall_raw())
+    }
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        Ok((v as i16).into_metacall_raw())
+    }
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        Ok(v.into_metacall_raw())
+    }
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        Ok(v.into_metacall_raw())
+    }
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        Ok(v.into_metacall_raw())
+    }
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        Ok((v as i16).into_metacall_raw())
+    }
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        Ok((v as i32).into_metacall_raw())
+    }
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        Ok((v as i64).into_metacall_raw())
+    }
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        match i64::try_from(v) {
+            Ok(v) => Ok(v.into_metacall_raw()),
+            Err(_) => Err(MetacallSerdeError::new(format!(
+                "{} doesn't fit into a Metacall long!",
+                v
+            ))),
+        }
+    }
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        Ok(v.into_metacall_raw())
+    }
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(v.into_metacall_raw())
+    }
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        create_string(v.encode_utf8(&mut [0; 4]))
+    }
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        create_string(v)
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(unsafe { metacall_value_create_buffer(v.as_ptr().cast(), v.len()) })
+    }
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(unsafe { metacall_value_create_null() })
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(unsafe { metacall_value_create_null() })
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.serialize_unit()
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        create_string(variant)
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        create_variant(variant, value.serialize(self)?)
+    }
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(MetacallSerializeArray {
+            values: Vec::with_capacity(len.unwrap_or(0)),
+            variant: None,
+        })
+    }
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Ok(MetacallSerializeArray {
+            values: Vec::with_capacity(len),
+            variant: Some(variant),
+        })
+    }
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(MetacallSerializeMap {
+            key: None,
+            pairs: Vec::with_capacity(len.unwrap_or(0)),
+            variant: None,
+        })
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.serialize_map(Some(len))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Ok(MetacallSerializeMap {
+            key: None,
+            pairs: Vec::with_capacity(len),
+            variant: Some(variant),
+        })
+    }
+}
+
+impl SerializeSeq for MetacallSerializeArray {
+    type Ok = *mut c_void;
+    type Error = MetacallSerdeError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+impl SerializeTuple for MetacallSerializeArray {
+    type Ok = *mut c_void;
+    type Error = MetacallSerdeError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+impl SerializeTupleStruct for MetacallSerializeArray {
+    type Ok = *mut c_void;
+    type Error = MetacallSerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+impl SerializeTupleVariant for MetacallSerializeArray {
+    type Ok = *mut c_void;
+    type Error = MetacallSerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+impl SerializeMap for MetacallSerializeMap {
+    type Ok = *mut c_void;
+    type Error = MetacallSerdeError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+        self.push_key(key)
+    }
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push_value(value)
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+impl SerializeStruct for MetacallSerializeMap {
+    type Ok = *mut c_void;
+    type Error = MetacallSerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.push_field(key, value)
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+impl SerializeStructVariant for MetacallSerializeMap {
+    type Ok = *mut c_void;
+    type Error = MetacallSerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.push_field(key, value)
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+/// Deserializer that converts a Metacall value into any [Deserialize](::serde::Deserialize)
+/// type. It accepts the same representation produced by [MetacallSerializer](MetacallSerializer)
+/// and integral doubles for integer fields, since some languages (like Javascript) only have
+/// doubles.
+pub struct MetacallDeserializer {
+    value: *mut c_void,
+}
+impl MetacallDeserializer {
+    #[doc(hidden)]
+    pub fn new(value: *mut c_void) -> Self {
+        Self { value }
+    }
+
+    fn id(&self) -> u32 {
+        if self.value.is_null() {
+            14
+        } else {
+            unsafe { metacall_value_id(self.value) }
+        }
+    }
+
+    fn string(&self) -> Result<&str, MetacallSerdeError> {
+        unsafe { CStr::from_ptr(metacall_value_to_string(self.value)) }
+            .to_str()
+            .map_err(MetacallSerdeError::new)
+    }
+
+    fn items(&self) -> &[*mut c_void] {
+        unsafe {
+            slice::from_raw_parts(
+                metacall_value_to_array(self.value),
+                metacall_value_count(self.value),
+            )
+        }
+    }
+
+    fn pairs(&self) -> &[*mut c_void] {
+        unsafe {
+            slice::from_raw_parts(
+                metacall_value_to_map(self.value),
+                metacall_value_count(self.value),
+            )
+        }
+    }
+
+    fn deserialize_integer<'de, V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, MetacallSerdeError> {
+        let number = match self.id() {
+            5 => (unsafe { metacall_value_to_float(self.value) }) as f64,
+            6 => unsafe { metacall_value_to_double(self.value) },
+            _ => return de::Deserializer::deserialize_any(self, visitor),
+        };
+
+        if number.fract() == 0.0 && number >= i64::MIN as f64 && number <= i64::MAX as f64 {
+            visitor.visit_i64(number as i64)
+        } else {
+            visitor.visit_f64(number)
+        }
+    }
+}
+
+macro_rules! deserialize_integers {
+    ($($method:ident),*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.deserialize_integer(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for MetacallDeserializer {
+    type Error = MetacallSerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.id() {
+            0 => visitor.visit_bool(unsafe { metacall_value_to_bool(self.value) } != 0),
+            1 => visitor.visit_char(unsafe { metacall_value_to_char(self.value) } as u8 as char),
+            2 => visitor.visit_i16(unsafe { metacall_value_to_short(self.value) }),
+            3 => visitor.visit_i32(unsafe { metacall_value_to_int(self.value) }),
+            4 => visitor.visit_i64(unsafe { metacall_value_to_long(self.value) }),
+            5 => visitor.visit_f32(unsafe { metacall_value_to_float(self.value) }),
+            6 => visitor.visit_f64(unsafe { metacall_value_to_double(self.value) }),
+            7 => visitor.visit_str(self.string()?),
+            8 => visitor.visit_bytes(unsafe {
+                slice::from_raw_parts(
+                    metacall_value_to_buffer(self.value) as *const u8,
+                    metacall_value_size(self.value),
+                )
+            }),
+            9 => visitor.visit_seq(MetacallSeqAccess {
+                items: self.items().iter(),
+            }),
+            10 => visitor.visit_map(MetacallMapAccess {
+                pairs: self.pairs().iter(),
+                value: None,
+            }),
+            14 => visitor.visit_unit(),
+            id => Err(MetacallSerdeError::new(format!(
+                "Metacall type with id `{}` cannot be deserialized!",
+                id
+            ))),
+        }
+    }
+
+    deserialize_integers!(
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64
+    );
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.id() == 14 {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.id() {
+            7 => visitor.visit_enum(self.string()?.into_deserializer()),
+            10 if self.pairs().len() == 1 => {
+                let pair =
+                    unsafe { slice::from_raw_parts(metacall_value_to_array(self.pairs()[0]), 2) };
+
+                visitor.visit_enum(MetacallEnumAccess {
+                    variant: pair[0],
+                    value: pair[1],
+                })
+            }
+            _ => Err(MetacallSerdeError::new(
+                "Expected a string or a map with a single key for an enum!",
+            )),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i128 u128 f32 f64 char str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct MetacallSeqAccess<'a> {
+    items: slice::Iter<'a, *mut c_void>,
+}
+impl<'de, 'a> SeqAccess<'de> for MetacallSeqAccess<'a> {
+    type Error = MetacallSerdeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        match self.items.next() {
+            Some(item) => seed.deserialize(MetacallDeserializer::new(*item)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct MetacallMapAccess<'a> {
+    pairs: slice::Iter<'a, *mut c_void>,
+    value: Option<*mut c_void>,
+}
+impl<'de, 'a> MapAccess<'de> for MetacallMapAccess<'a> {
+    type Error = MetacallSerdeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.pairs.next() {
+            Some(pair) => {
+                let pair = unsafe { slice::from_raw_parts(metacall_value_to_array(*pair), 2) };
+                self.value = Some(pair[1]);
+
+                seed.deserialize(MetacallDeserializer::new(pair[0]))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(MetacallDeserializer::new(value)),
+            None => Err(MetacallSerdeError::new(
+                "Map value requested before its key!",
+            )),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.pairs.len())
+    }
+}
+
+struct MetacallEnumAccess {
+    variant: *mut c_void,
+    value: *mut c_void,
+}
+impl<'de> EnumAccess<'de> for MetacallEnumAccess {
+    type Error = MetacallSerdeError;
+    type Variant = MetacallDeserializer;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(MetacallDeserializer::new(self.variant))?;
+
+        Ok((variant, MetacallDeserializer::new(self.value)))
+    }
+}
+impl<'de> VariantAccess<'de> for MetacallDeserializer {
+    type Error = MetacallSerdeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self)
+    }
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
+
+/// Serializes a value into a raw Metacall value owned by the caller.
+#[doc(hidden)]
+pub fn to_metacall_raw<T: ?Sized + Serialize>(
+    value: &T,
+) -> Result<*mut c_void, MetacallSerdeError> {
+    value.serialize(MetacallSerializer)
+}
+
+// Serializes the value for `into_metacall_raw`, which can't return the error, so it fails the
+// conversion in progress instead.
+#[doc(hidden)]
+pub fn to_metacall_raw_or_failure<T: ?Sized + Serialize>(value: &T) -> *mut c_void {
+    to_metacall_raw(value)
+        .unwrap_or_else(|err| parsers::conversion_failure(MetacallConversionError::new(err)))
+}
+
+/// Deserializes a raw Metacall value without taking its ownership.
+#[doc(hidden)]
+pub fn from_metacall_raw<T: DeserializeOwned>(value: *mut c_void) -> Result<T, MetacallSerdeError> {
+    T::deserialize(MetacallDeserializer::new(value))
+}
+
+#[doc(hidden)]
+pub fn raw_to_metacallobj_untyped_leak(value: *mut c_void) -> Box<dyn MetacallValue> {
+    parsers::raw_to_metacallobj_untyped_leak(value)
+}
+
+#[macro_export]
+/// Implements [MetacallValue](crate::MetacallValue) for types implementing serde's `Serialize`
+/// and `Deserialize`, so they can be used as arguments and return types. The types must also
+/// implement `Clone` and `Debug`. Requires the `serde` feature. Calls fail with
+/// `FailedConversion` error if a value can't be serialized. For example: ...
+/// ```
+/// use metacall::{metacall, metacall_serde};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Clone, Serialize, Deserialize)]
+/// struct UserRequest {
+///     id: i64,
+/// }
+///
+/// #[derive(Debug, Clone, Serialize, Deserialize)]
+/// struct User {
+///     name: String,
+///     tags: Vec<String>,
+/// }
+///
+/// metacall_serde!(UserRequest, User);
+///
+/// let user = metacall::<User>("get_user", [UserRequest { id: 1 }]).unwrap();
+/// ```
+macro_rules! metacall_serde {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl $crate::MetacallValue for $type {
+                fn get_metacall_id() -> u32 {
+                    // Map, although any value is accepted and validated by the deserializer
+                    10
+                }
+                fn accepts_metacall_id(_id: u32) -> bool {
+                    true
+                }
+                fn from_metacall_raw_leak(
+                    v: *mut ::std::ffi::c_void,
+                ) -> Result<Self, Box<dyn $crate::MetacallValue>> {
+                    $crate::serde::from_metacall_raw::<Self>(v)
+                        .map_err(|_| $crate::serde::raw_to_metacallobj_untyped_leak(v))
+                }
+                fn into_metacall_raw(self) -> *mut ::std::ffi::c_void {
+                    $crate::serde::to_metacall_raw_or_failure(&self)
+                }
+            }
+        )*
+    };
+}
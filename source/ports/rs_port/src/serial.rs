@@ -0,0 +1,135 @@
+use crate::{
+    bindings::{metacall_deserialize, metacall_serial, metacall_serialize, metacall_value_destroy},
+    check_initialized, cstring_enum, parsers,
+    types::{MetacallAllocator, MetacallSerialError, MetacallValue},
+};
+use std::ffi::{c_void, CStr};
+
+/// Name of the serializer based on RapidJSON, used by [to_json](to_json) and
+/// [from_json](from_json).
+pub const RAPID_JSON: &str = "rapid_json";
+
+/// Gets the name of the serializer Metacall was built with, which is used internally for
+/// configurations and inspection.
+pub fn default_serializer() -> String {
+    String::from(
+        unsafe { CStr::from_ptr(metacall_serial()) }
+            .to_str()
+            .unwrap(),
+    )
+}
+
+/// Serializes a value with the given serializer. Usage example: ...
+/// ```
+/// let serialized = metacall::serial::serialize("rapid_json", vec![1, 2, 3]).unwrap();
+///
+/// assert_eq!(serialized, "[1,2,3]");
+/// ```
+pub fn serialize(
+    serializer: impl ToString,
+    value: impl MetacallValue,
+) -> Result<String, MetacallSerialError> {
+    check_initialized!(MetacallSerialError);
+    let c_serializer = cstring_enum!(serializer, MetacallSerialError)?;
+    let allocator = MetacallAllocator::std();
+    let c_value =
+        parsers::metacallobj_to_raw(value).map_err(MetacallSerialError::FailedConversion)?;
+
+    let mut size = 0;
+    let buffer = unsafe {
+        metacall_serialize(
+            c_serializer.as_ptr(),
+            c_value,
+            &mut size,
+            allocator.as_ptr(),
+        )
+    };
+
+    unsafe { metacall_value_destroy(c_value) };
+
+    if buffer.is_null() {
+        return Err(MetacallSerialError::SerializeFailure);
+    }
+
+    let serialized = unsafe { CStr::from_ptr(buffer) }
+        .to_string_lossy()
+        .into_owned();
+    unsafe { allocator.free(buffer.cast()) };
+
+    Ok(serialized)
+}
+
+fn deserialize_inner(
+    serializer: impl ToString,
+    buffer: impl AsRef<str>,
+) -> Result<*mut c_void, MetacallSerialError> {
+    check_initialized!(MetacallSerialError);
+    let c_serializer = cstring_enum!(serializer, MetacallSerialError)?;
+    let c_buffer = cstring_enum!(buffer.as_ref(), MetacallSerialError)?;
+    let allocator = MetacallAllocator::std();
+
+    // The size includes the null character
+    let value = unsafe {
+        metacall_deserialize(
+            c_serializer.as_ptr(),
+            c_buffer.as_ptr(),
+            c_buffer.as_bytes_with_nul().len(),
+            allocator.as_ptr(),
+        )
+    };
+
+    if value.is_null() {
+        return Err(MetacallSerialError::DeserializeFailure);
+    }
+
+    Ok(value)
+}
+/// Deserializes a value with the given serializer without type
+/// casting([MetacallValue](MetacallValue)).
+pub fn deserialize_untyped(
+    serializer: impl ToString,
+    buffer: impl AsRef<str>,
+) -> Result<Box<dyn MetacallValue>, MetacallSerialError> {
+    Ok(parsers::raw_to_metacallobj_untyped(deserialize_inner(
+        serializer, buffer,
+    )?))
+}
+/// Deserializes a value with the given serializer. Usage example: ...
+/// ```
+/// let numbers = metacall::serial::deserialize::<Vec<i32>>("rapid_json", "[1, 2, 3]").unwrap();
+///
+/// assert_eq!(numbers, vec![1, 2, 3]);
+/// ```
+pub fn deserialize<T: MetacallValue>(
+    serializer: impl ToString,
+    buffer: impl AsRef<str>,
+) -> Result<T, MetacallSerialError> {
+    parsers::raw_to_metacallobj::<T>(deserialize_inner(serializer, buffer)?)
+        .map_err(MetacallSerialError::FailedCasting)
+}
+
+/// Serializes a value as JSON. Usage example: ...
+/// ```
+/// let args = vec![String::from("a"), String::from("b")];
+/// println!("Calling with: {}", metacall::serial::to_json(args.clone()).unwrap());
+///
+/// metacall::metacall::<String>("concat", args).unwrap();
+/// ```
+pub fn to_json(value: impl MetacallValue) -> Result<String, MetacallSerialError> {
+    serialize(RAPID_JSON, value)
+}
+/// Deserializes a value from JSON without type casting([MetacallValue](MetacallValue)).
+pub fn from_json_untyped(
+    json: impl AsRef<str>,
+) -> Result<Box<dyn MetacallValue>, MetacallSerialError> {
+    deserialize_untyped(RAPID_JSON, json)
+}
+/// Deserializes a value from JSON. Usage example: ...
+/// ```
+/// use std::collections::HashMap;
+///
+/// let user = metacall::serial::from_json::<HashMap<String, String>>(r#"{"name": "John"}"#).unwrap();
+/// ```
+pub fn from_json<T: MetacallValue>(json: impl AsRef<str>) -> Result<T, MetacallSerialError> {
+    deserialize::<T>(RAPID_JSON, json)
+}
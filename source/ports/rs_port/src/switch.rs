@@ -1,24 +1,111 @@
 use crate::{
-    bindings::{metacall_destroy, metacall_initialize},
-    types::MetacallInitError,
+    bindings::{
+        metacall_destroy, metacall_execution_path, metacall_flags, metacall_fork,
+        metacall_fork_destroy, metacall_fork_initialize, metacall_initialize,
+        metacall_initialize_args, metacall_initialize_configuration_type, metacall_initialize_ex,
+        metacall_is_initialized, metacall_log, metacall_log_file_type,
+        metacall_log_id_METACALL_LOG_FILE, metacall_log_id_METACALL_LOG_SOCKET,
+        metacall_log_id_METACALL_LOG_STDIO, metacall_log_id_METACALL_LOG_SYSLOG,
+        metacall_log_socket_type, metacall_log_stdio_type, metacall_log_syslog_type, stderr,
+        stdout, METACALL_FLAGS_FORK_SAFE,
+    },
+    cstring_enum,
+    types::{MetacallBuilderError, MetacallForkError, MetacallInitError, MetacallValue},
 };
-use std::ffi::c_int;
+use std::{
+    ffi::{c_char, c_int, c_void, CString},
+    fmt::{self, Debug, Formatter},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    ptr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+// Number of alive guards of the runtime, it gets destroyed when the last one is dropped.
+static REFERENCES: Mutex<usize> = Mutex::new(0);
+// Incremented every time the runtime is destroyed, so values created by a previous runtime can
+// be told apart from the current ones.
+static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+// Closures run around the next fork, taken by the callbacks when the process gets forked.
+type MetacallPreFork = Box<dyn FnOnce() + Send>;
+type MetacallPostFork = Box<dyn FnOnce(i32) + Send>;
+static FORK_CALLBACKS: Mutex<(Option<MetacallPreFork>, Option<MetacallPostFork>)> =
+    Mutex::new((None, None));
 
 pub fn destroy_manually() -> c_int {
-    unsafe { metacall_destroy() }
+    let result = unsafe { metacall_destroy() };
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+
+    // The fork detour is installed in the whole process and would initialize Metacall again on
+    // the next fork, so it's removed along with the runtime. It's a no-op if not installed
+    unsafe { metacall_fork_destroy() };
+
+    result
 }
 pub fn initialize_manually() -> c_int {
     unsafe { metacall_initialize() }
 }
 
-pub struct MetacallAutoDestroy(pub fn() -> c_int);
+// Checks if Metacall is initialized, no matter who initialized it.
+pub(crate) fn is_initialized() -> bool {
+    unsafe { metacall_is_initialized(ptr::null()) == 0 }
+}
+
+// Runtime in which a value was created. Values of a destroyed runtime refer to memory freed by
+// the loaders, so they must not be used or destroyed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MetacallGeneration(usize);
+impl MetacallGeneration {
+    pub(crate) fn current() -> Self {
+        Self(GENERATION.load(Ordering::SeqCst))
+    }
+
+    // Checks if the runtime in which the value was created is still alive.
+    pub(crate) fn is_alive(self) -> bool {
+        self == Self::current() && is_initialized()
+    }
+}
+
+/// Guard of the Metacall runtime returned by [initialize](initialize) or
+/// [MetacallBuilder](MetacallBuilder). It's reference counted, so cloning it or initializing
+/// again gives another guard of the same runtime, and Metacall gets destroyed when the last one
+/// is dropped. After that, calls return a `NotInitialized` error and the values of the runtime,
+/// such as [objects](crate::MetacallObject) or [functions](crate::MetacallFunction), can't be
+/// used anymore.
+pub struct MetacallAutoDestroy {
+    _private: (),
+}
+impl Clone for MetacallAutoDestroy {
+    fn clone(&self) -> Self {
+        *REFERENCES.lock().unwrap() += 1;
+
+        Self { _private: () }
+    }
+}
+impl Debug for MetacallAutoDestroy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetacallAutoDestroy")
+            .field("references", &*REFERENCES.lock().unwrap())
+            .finish()
+    }
+}
 impl Drop for MetacallAutoDestroy {
     fn drop(&mut self) {
-        self.0();
+        let mut references = REFERENCES.lock().unwrap();
+        *references -= 1;
+
+        if *references == 0 {
+            destroy_manually();
+        }
     }
 }
 
 /// Initializes Metacall. Always remember to store the output in a variable to avoid instant drop.
+/// If Metacall is already initialized, another guard of the same runtime is returned.
 /// For example: ...
 /// ```
 /// // Initialize metacall at the top of your main function before loading your codes or
@@ -28,9 +115,314 @@ impl Drop for MetacallAutoDestroy {
 ///
 /// ```
 pub fn initialize() -> Result<MetacallAutoDestroy, MetacallInitError> {
-    if initialize_manually() != 0 {
+    let mut references = REFERENCES.lock().unwrap();
+
+    if *references == 0 && initialize_manually() != 0 {
         return Err(MetacallInitError::new());
     }
+    *references += 1;
+
+    Ok(MetacallAutoDestroy { _private: () })
+}
+
+unsafe extern "C" fn pre_fork(_: *mut c_void) -> c_int {
+    // The core destroys the runtime right after this callback and initializes it again after
+    // the fork, so the values of the current one can't be used anymore
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+
+    let pre = FORK_CALLBACKS.lock().unwrap().0.take();
+    if let Some(pre) = pre {
+        // Panics can't unwind through the core, the failure is logged by it instead
+        if panic::catch_unwind(AssertUnwindSafe(pre)).is_err() {
+            return 1;
+        }
+    }
+
+    0
+}
+unsafe extern "C" fn post_fork(pid: i32, _: *mut c_void) -> c_int {
+    let post = FORK_CALLBACKS.lock().unwrap().1.take();
+    if let Some(post) = post {
+        if panic::catch_unwind(AssertUnwindSafe(|| post(pid))).is_err() {
+            return 1;
+        }
+    }
+
+    0
+}
+
+/// Forks the process without corrupting the runtimes, such as NodeJS or Python. Metacall is
+/// destroyed before forking and initialized again with the default configuration in both
+/// processes, so the scripts must be loaded again and the values of the previous runtime can't
+/// be used anymore. The guards of the runtime are still valid. `pre` is run in the parent before
+/// forking and `post` is run in both processes after, receiving the process id returned by the
+/// fork, which is zero in the child. The process id is returned as well. A panic in the
+/// callbacks is caught and logged by Metacall, without stopping the fork.
+///
+/// Keep in mind that it replaces the `fork` function of the whole process with a detour of
+/// Metacall, so any other fork done while the runtime is alive goes through it too, destroying
+/// and initializing Metacall again. The detour is removed when the runtime is destroyed.
+///
+/// Usage example: ...
+/// ```
+/// let pid = metacall::fork(
+///     || println!("Forking..."),
+///     |_| {
+///         metacall::loaders::from_single_file("node", "worker.js").unwrap();
+///     },
+/// )
+/// .unwrap();
+///
+/// if pid == 0 {
+///     metacall::metacall_no_arg::<metacall::MetacallNull>("work").unwrap();
+/// }
+/// ```
+#[cfg(unix)]
+pub fn fork(
+    pre: impl FnOnce() + Send + 'static,
+    post: impl FnOnce(i32) + Send + 'static,
+) -> Result<i32, MetacallForkError> {
+    if !is_initialized() {
+        return Err(MetacallForkError::NotInitialized);
+    }
+    // It's a no-op if the runtime was initialized as fork safe
+    if unsafe { metacall_fork_initialize() } != 0 {
+        return Err(MetacallForkError::DetourFailure);
+    }
+
+    *FORK_CALLBACKS.lock().unwrap() = (Some(Box::new(pre)), Some(Box::new(post)));
+    unsafe { metacall_fork(Some(pre_fork), Some(post_fork)) };
+
+    let pid = unsafe { crate::bindings::fork() };
+    if pid < 0 {
+        return Err(MetacallForkError::ForkFailure);
+    }
+
+    Ok(pid)
+}
+
+#[derive(Debug, Clone)]
+/// Destination of the Metacall logs. Checkout [MetacallBuilder](MetacallBuilder) for usage.
+pub enum MetacallLog {
+    /// Writes the logs to the standard output.
+    Stdout,
+    /// Writes the logs to the standard error.
+    Stderr,
+    /// Appends the logs to a file.
+    File(PathBuf),
+    /// Sends the logs to the system logger with the given name.
+    Syslog(String),
+    /// Sends the logs to a socket.
+    Socket { ip: String, port: u16 },
+}
+impl MetacallLog {
+    fn configure(&self) -> Result<(), MetacallBuilderError> {
+        // The core keeps a reference to the strings, so they live as long as the process
+        fn leak(string: impl ToString) -> Result<*const c_char, MetacallBuilderError> {
+            Ok(cstring_enum!(string, MetacallBuilderError)?.into_raw())
+        }
+
+        let result = unsafe {
+            match self {
+                Self::Stdout => metacall_log(
+                    metacall_log_id_METACALL_LOG_STDIO,
+                    &mut metacall_log_stdio_type { stream: stdout } as *mut _ as *mut c_void,
+                ),
+                Self::Stderr => metacall_log(
+                    metacall_log_id_METACALL_LOG_STDIO,
+                    &mut metacall_log_stdio_type { stream: stderr } as *mut _ as *mut c_void,
+                ),
+                Self::File(path) => metacall_log(
+                    metacall_log_id_METACALL_LOG_FILE,
+                    &mut metacall_log_file_type {
+                        file_name: leak(path.display())?,
+                        mode: leak("a")?,
+                    } as *mut _ as *mut c_void,
+                ),
+                Self::Syslog(name) => metacall_log(
+                    metacall_log_id_METACALL_LOG_SYSLOG,
+                    &mut metacall_log_syslog_type { name: leak(name)? } as *mut _ as *mut c_void,
+                ),
+                Self::Socket { ip, port } => metacall_log(
+                    metacall_log_id_METACALL_LOG_SOCKET,
+                    &mut metacall_log_socket_type {
+                        ip: leak(ip)?,
+                        port: *port,
+                    } as *mut _ as *mut c_void,
+                ),
+            }
+        };
+
+        if result != 0 {
+            return Err(MetacallBuilderError::LogFailure);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+/// Configures Metacall before initializing it. Same as [initialize](initialize), always remember
+/// to store the output in a variable to avoid instant drop. Keep in mind that the arguments and
+/// the loader options are kept by Metacall until the process exits. For example: ...
+/// ```
+/// use metacall::{MetacallBuilder, MetacallLog};
+/// use std::collections::HashMap;
+///
+/// let mut node_options = HashMap::new();
+/// node_options.insert(String::from("max_old_space_size"), 4096);
+///
+/// let _metacall = MetacallBuilder::new()
+///     .log(MetacallLog::Stderr)
+///     .fork_safe(true)
+///     .loader_options("node", node_options)
+///     .args(std::env::args())
+///     .execution_path("py", "./scripts")
+///     .initialize()
+///     .unwrap();
+/// ```
+pub struct MetacallBuilder {
+    args: Vec<String>,
+    execution_paths: Vec<(String, PathBuf)>,
+    fork_safe: bool,
+    loader_options: Vec<(String, Box<dyn MetacallValue>)>,
+    log: Option<MetacallLog>,
+}
+impl MetacallBuilder {
+    /// Creates a builder with the default configuration, same as [initialize](initialize).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets where the logs are written.
+    pub fn log(mut self, log: MetacallLog) -> Self {
+        self.log = Some(log);
+
+        self
+    }
+    /// Makes Metacall safe to use across forks of the process, even the ones not done through
+    /// [fork](fork). It's disabled by default, even if a previous runtime enabled it.
+    pub fn fork_safe(mut self, fork_safe: bool) -> Self {
+        self.fork_safe = fork_safe;
 
-    Ok(MetacallAutoDestroy(destroy_manually))
+        self
+    }
+    /// Sets the initialization options of a loader, for example a map of settings.
+    pub fn loader_options(mut self, tag: impl ToString, options: impl MetacallValue) -> Self {
+        self.loader_options
+            .push((tag.to_string(), Box::new(options) as Box<dyn MetacallValue>));
+
+        self
+    }
+    /// Forwards command-line arguments to the runtimes, for example the ones from
+    /// [std::env::args](std::env::args).
+    pub fn args(mut self, args: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.args
+            .extend(args.into_iter().map(|arg| arg.to_string()));
+
+        self
+    }
+    /// Adds a path where the scripts of a loader are looked up.
+    pub fn execution_path(mut self, tag: impl ToString, path: impl AsRef<Path>) -> Self {
+        self.execution_paths
+            .push((tag.to_string(), path.as_ref().to_path_buf()));
+
+        self
+    }
+
+    fn initialize_inner(self) -> Result<(), MetacallBuilderError> {
+        if let Some(log) = &self.log {
+            log.configure()?;
+        }
+
+        // The flags are kept by the core across runtimes, so they're cleared if not set
+        let flags = if self.fork_safe {
+            METACALL_FLAGS_FORK_SAFE as c_int
+        } else {
+            0
+        };
+        unsafe { metacall_flags(flags) };
+
+        if !self.args.is_empty() {
+            let mut argv = self
+                .args
+                .iter()
+                .map(|arg| Ok(cstring_enum!(arg, MetacallBuilderError)?.into_raw()))
+                .collect::<Result<Vec<*mut c_char>, MetacallBuilderError>>()?;
+            let argc = argv.len() as c_int;
+            argv.push(ptr::null_mut());
+
+            // The core keeps a reference to the arguments
+            unsafe {
+                metacall_initialize_args(argc, Box::leak(argv.into_boxed_slice()).as_mut_ptr())
+            };
+        }
+
+        if self.loader_options.is_empty() {
+            if initialize_manually() != 0 {
+                return Err(MetacallBuilderError::InitFailure);
+            }
+
+            return Ok(());
+        }
+
+        let mut config = self
+            .loader_options
+            .into_iter()
+            .map(|(tag, options)| {
+                Ok(metacall_initialize_configuration_type {
+                    tag: cstring_enum!(tag, MetacallBuilderError)?.into_raw(),
+                    // The loader keeps the options without destroying them
+                    options: options.into_metacall_raw_box(),
+                })
+            })
+            .collect::<Result<Vec<_>, MetacallBuilderError>>()?;
+        config.push(metacall_initialize_configuration_type {
+            tag: ptr::null_mut(),
+            options: ptr::null_mut(),
+        });
+
+        let result = unsafe { metacall_initialize_ex(config.as_mut_ptr()) };
+
+        for entry in config.into_iter().filter(|entry| !entry.tag.is_null()) {
+            drop(unsafe { CString::from_raw(entry.tag) });
+        }
+
+        if result != 0 {
+            // Metacall may be initialized even if a loader failed
+            destroy_manually();
+
+            return Err(MetacallBuilderError::InitFailure);
+        }
+
+        Ok(())
+    }
+    /// Initializes Metacall with the configuration. Metacall gets destroyed if anything fails
+    /// after it's initialized. It fails with `AlreadyInitialized` if there's a guard of the
+    /// runtime alive, as the configuration can't be applied anymore.
+    pub fn initialize(mut self) -> Result<MetacallAutoDestroy, MetacallBuilderError> {
+        let execution_paths = std::mem::take(&mut self.execution_paths);
+
+        {
+            let mut references = REFERENCES.lock().unwrap();
+            if *references != 0 {
+                return Err(MetacallBuilderError::AlreadyInitialized);
+            }
+
+            self.initialize_inner()?;
+            *references = 1;
+        }
+        let metacall = MetacallAutoDestroy { _private: () };
+
+        for (tag, path) in execution_paths {
+            let c_tag = cstring_enum!(tag, MetacallBuilderError)?;
+            let c_path = cstring_enum!(path.display(), MetacallBuilderError)?;
+
+            if unsafe { metacall_execution_path(c_tag.as_ptr(), c_path.as_ptr()) } != 0 {
+                return Err(MetacallBuilderError::ExecutionPathFailure(path));
+            }
+        }
+
+        Ok(metacall)
+    }
 }
@@ -0,0 +1,367 @@
+use crate::bindings::{
+    free, malloc, metacall_allocator_alloc, metacall_allocator_create, metacall_allocator_destroy,
+    metacall_allocator_free, metacall_allocator_id_METACALL_ALLOCATOR_NGINX,
+    metacall_allocator_id_METACALL_ALLOCATOR_STD, metacall_allocator_nginx_type,
+    metacall_allocator_realloc, metacall_allocator_std_type, metacall_value_allocator, ngx_pool_t,
+    realloc,
+};
+use std::{
+    alloc::{self, GlobalAlloc, Layout},
+    any::Any,
+    cell::{Cell, RefCell},
+    ffi::c_void,
+    fmt::{self, Debug, Formatter},
+    ptr,
+};
+
+/// Signature of the nginx `palloc` function, which allocates memory from a pool.
+pub type MetacallPoolAlloc = unsafe extern "C" fn(*mut ngx_pool_t, usize) -> *mut c_void;
+/// Signature of the nginx `pcopy` function, which copies memory between two blocks.
+pub type MetacallPoolCopy = unsafe extern "C" fn(*mut c_void, *const c_void, usize) -> *mut c_void;
+/// Signature of the nginx `pfree` function, which gives a block back to its pool.
+pub type MetacallPoolFree = unsafe extern "C" fn(*mut ngx_pool_t, *mut c_void) -> isize;
+
+unsafe extern "C" fn std_malloc(size: usize) -> *mut c_void {
+    malloc(size as _)
+}
+unsafe extern "C" fn std_realloc(data: *mut c_void, size: usize) -> *mut c_void {
+    realloc(data, size as _)
+}
+unsafe extern "C" fn std_free(data: *mut c_void) {
+    free(data)
+}
+
+// Size of the header storing the size of each block given by a Rust allocator, as it's needed
+// to free the block. It also keeps the blocks aligned the same way malloc does.
+const HEADER_SIZE: usize = 16;
+
+unsafe extern "C" fn global_palloc<A: GlobalAlloc>(
+    pool: *mut ngx_pool_t,
+    size: usize,
+) -> *mut c_void {
+    let allocator = &*(pool as *const A);
+    let layout = match size
+        .checked_add(HEADER_SIZE)
+        .map(|total_size| Layout::from_size_align(total_size, HEADER_SIZE))
+    {
+        Some(Ok(layout)) => layout,
+        _ => return ptr::null_mut(),
+    };
+
+    let block = allocator.alloc(layout);
+    if block.is_null() {
+        return ptr::null_mut();
+    }
+    (block as *mut usize).write(size);
+
+    block.add(HEADER_SIZE).cast()
+}
+unsafe extern "C" fn global_pcopy(
+    dest: *mut c_void,
+    src: *const c_void,
+    size: usize,
+) -> *mut c_void {
+    ptr::copy_nonoverlapping(src as *const u8, dest as *mut u8, size);
+
+    dest
+}
+unsafe extern "C" fn global_pfree<A: GlobalAlloc>(
+    pool: *mut ngx_pool_t,
+    data: *mut c_void,
+) -> isize {
+    if data.is_null() {
+        return 0;
+    }
+
+    let allocator = &*(pool as *const A);
+    let block = (data as *mut u8).sub(HEADER_SIZE);
+    let size = (block as *const usize).read();
+    allocator.dealloc(
+        block,
+        Layout::from_size_align_unchecked(size + HEADER_SIZE, HEADER_SIZE),
+    );
+
+    0
+}
+
+/// Represents Metacall allocator, which is used by the core to allocate the buffers it gives
+/// back, such as the ones of serialization and inspection, and the values created within a
+/// [scope](#method.scope). Memory can come from the C standard library, an nginx pool or any
+/// Rust [GlobalAlloc](std::alloc::GlobalAlloc), such as [MetacallArena](MetacallArena), so
+/// request-scoped values can be freed in bulk. For example: ...
+/// ```
+/// use metacall::{MetacallAllocator, MetacallArena};
+///
+/// let allocator = MetacallAllocator::from_global_alloc(MetacallArena::new());
+///
+/// let buffer = allocator.alloc(64);
+/// let sum = unsafe { allocator.scope(|| metacall::metacall::<i32>("sum", [1, 2])) }.unwrap();
+///
+/// // Everything allocated is freed along with the arena
+/// drop(allocator);
+/// ```
+pub struct MetacallAllocator {
+    allocator: *mut c_void,
+    // The Rust allocator the memory comes from, which must outlive the Metacall one.
+    _source: Option<Box<dyn Any>>,
+}
+impl Debug for MetacallAllocator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetacallAllocator")
+            .field("allocator", &self.allocator)
+            .finish()
+    }
+}
+
+impl MetacallAllocator {
+    fn create(id: u32, ctx: *mut c_void) -> Self {
+        let allocator = unsafe { metacall_allocator_create(id, ctx) };
+        if allocator.is_null() {
+            panic!("Failed to create the Metacall allocator!");
+        }
+
+        Self {
+            allocator,
+            _source: None,
+        }
+    }
+
+    /// Creates an allocator backed by the C standard library. It's the one used by the port
+    /// internally.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the allocator can't be created.
+    pub fn std() -> Self {
+        let mut std_ctx = metacall_allocator_std_type {
+            malloc: Some(std_malloc),
+            realloc: Some(std_realloc),
+            free: Some(std_free),
+        };
+
+        Self::create(
+            metacall_allocator_id_METACALL_ALLOCATOR_STD,
+            &mut std_ctx as *mut _ as *mut c_void,
+        )
+    }
+
+    /// Creates an allocator that takes its memory from an nginx pool.
+    ///
+    /// # Safety
+    ///
+    /// The pool must outlive the allocator and the functions must behave as the nginx ones, as
+    /// the allocator itself is allocated from the pool.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the allocator can't be created.
+    pub unsafe fn nginx(
+        pool: *mut ngx_pool_t,
+        palloc: MetacallPoolAlloc,
+        pcopy: MetacallPoolCopy,
+        pfree: MetacallPoolFree,
+    ) -> Self {
+        let mut nginx_ctx = metacall_allocator_nginx_type {
+            pool,
+            palloc: Some(palloc),
+            pcopy: Some(pcopy),
+            pfree: Some(pfree),
+        };
+
+        Self::create(
+            metacall_allocator_id_METACALL_ALLOCATOR_NGINX,
+            &mut nginx_ctx as *mut _ as *mut c_void,
+        )
+    }
+
+    /// Creates an allocator that takes its memory from a Rust allocator, which is owned by the
+    /// Metacall allocator and dropped along with it. For example: ...
+    /// ```
+    /// let allocator = metacall::MetacallAllocator::from_global_alloc(std::alloc::System);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the allocator can't be created.
+    pub fn from_global_alloc<A: GlobalAlloc + 'static>(allocator: A) -> Self {
+        let source = Box::new(allocator);
+        // The pool is the Rust allocator itself, which doesn't move as it's boxed
+        let pool = &*source as *const A as *mut ngx_pool_t;
+
+        unsafe {
+            let mut allocator =
+                Self::nginx(pool, global_palloc::<A>, global_pcopy, global_pfree::<A>);
+            allocator._source = Some(source);
+
+            allocator
+        }
+    }
+
+    /// Allocates a block of the given size. It's null if the allocation fails.
+    pub fn alloc(&self, size: usize) -> *mut c_void {
+        unsafe { metacall_allocator_alloc(self.allocator, size) }
+    }
+    /// Resizes a block allocated by this allocator. It's null if the allocation fails, leaving
+    /// the block untouched.
+    ///
+    /// # Safety
+    ///
+    /// The block must be allocated by this allocator with the given size.
+    pub unsafe fn realloc(&self, data: *mut c_void, size: usize, new_size: usize) -> *mut c_void {
+        metacall_allocator_realloc(self.allocator, data, size, new_size)
+    }
+    /// Frees a block allocated by this allocator.
+    ///
+    /// # Safety
+    ///
+    /// The block must be allocated by this allocator, and it must not be used afterwards.
+    pub unsafe fn free(&self, data: *mut c_void) {
+        metacall_allocator_free(self.allocator, data)
+    }
+
+    /// Runs the closure with the values created in the current thread allocated by this allocator,
+    /// such as the arguments and return values of the calls made in it, and the values created
+    /// by the loaders running in the current thread. Each value is freed through the allocator it
+    /// was created with, so an arena frees all of them at once when it's dropped.
+    ///
+    /// # Safety
+    ///
+    /// The values created in the closure must not outlive the allocator. The arguments and return
+    /// values of calls are destroyed before the call returns, but the objects, functions, classes,
+    /// futures and pointers obtained in the closure keep their values, as the loaders may do with
+    /// the values they create.
+    pub unsafe fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        // Sets the previous allocator back even if the closure panics
+        struct Restore(*mut c_void);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                unsafe { metacall_value_allocator(self.0) };
+            }
+        }
+
+        let _restore = Restore(metacall_value_allocator(self.allocator));
+
+        f()
+    }
+
+    /// Gets the raw pointer of the allocator, which can be passed to the core.
+    pub fn as_ptr(&self) -> *mut c_void {
+        self.allocator
+    }
+}
+
+impl Drop for MetacallAllocator {
+    fn drop(&mut self) {
+        unsafe { metacall_allocator_destroy(self.allocator) }
+    }
+}
+
+/// Bump allocator that never frees memory one block at a time, but all at once when it's
+/// dropped. Useful along with [MetacallAllocator](MetacallAllocator) for short-lived,
+/// request-scoped values and buffers. For example: ...
+/// ```
+/// use metacall::{MetacallAllocator, MetacallArena};
+///
+/// for request in requests {
+///     let allocator = MetacallAllocator::from_global_alloc(MetacallArena::new());
+///
+///     handle(request, &allocator);
+/// }
+/// ```
+pub struct MetacallArena {
+    chunk_size: usize,
+    chunks: RefCell<Vec<(*mut u8, Layout)>>,
+    // Offset of the free memory in the last chunk.
+    offset: Cell<usize>,
+    allocated: Cell<usize>,
+}
+impl Debug for MetacallArena {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetacallArena")
+            .field("chunk_size", &self.chunk_size)
+            .field("chunks", &self.chunks.borrow().len())
+            .field("allocated", &self.allocated.get())
+            .finish()
+    }
+}
+impl Default for MetacallArena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetacallArena {
+    /// Size of the chunks of an arena created with [new](#method.new).
+    pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+    /// Creates an empty arena. Memory is requested from the system in chunks of
+    /// [DEFAULT_CHUNK_SIZE](#associatedconstant.DEFAULT_CHUNK_SIZE) bytes.
+    pub fn new() -> Self {
+        Self::with_chunk_size(Self::DEFAULT_CHUNK_SIZE)
+    }
+    /// Creates an empty arena that requests memory from the system in chunks of the given
+    /// size. Blocks bigger than a chunk get a chunk of their own.
+    pub fn with_chunk_size(chunk_size: usize) -> Self {
+        Self {
+            chunk_size,
+            chunks: RefCell::new(Vec::new()),
+            offset: Cell::new(0),
+            allocated: Cell::new(0),
+        }
+    }
+
+    /// Gets the number of bytes given by the arena so far.
+    pub fn allocated(&self) -> usize {
+        self.allocated.get()
+    }
+}
+
+unsafe impl GlobalAlloc for MetacallArena {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let mut chunks = self.chunks.borrow_mut();
+
+        if let Some(&(chunk, chunk_layout)) = chunks.last() {
+            let start = (chunk as usize + self.offset.get()).next_multiple_of(layout.align())
+                - chunk as usize;
+
+            if let Some(end) = start.checked_add(layout.size()) {
+                if end <= chunk_layout.size() {
+                    self.offset.set(end);
+                    self.allocated.set(self.allocated.get() + layout.size());
+
+                    return chunk.add(start);
+                }
+            }
+        }
+
+        let chunk_layout = match Layout::from_size_align(
+            layout.size().max(self.chunk_size).max(1),
+            layout.align().max(HEADER_SIZE),
+        ) {
+            Ok(chunk_layout) => chunk_layout,
+            Err(_) => return ptr::null_mut(),
+        };
+        let chunk = alloc::alloc(chunk_layout);
+        if chunk.is_null() {
+            return chunk;
+        }
+
+        chunks.push((chunk, chunk_layout));
+        self.offset.set(layout.size());
+        self.allocated.set(self.allocated.get() + layout.size());
+
+        chunk
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {
+        // Memory is freed all at once when the arena is dropped
+    }
+}
+
+impl Drop for MetacallArena {
+    fn drop(&mut self) {
+        for (chunk, chunk_layout) in self.chunks.get_mut().drain(..) {
+            unsafe { alloc::dealloc(chunk, chunk_layout) };
+        }
+    }
+}
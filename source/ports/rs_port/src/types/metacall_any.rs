@@ -0,0 +1,126 @@
+use super::{
+    MetacallBuffer, MetacallClass, MetacallException, MetacallFunction, MetacallFuture,
+    MetacallMap, MetacallNull, MetacallObject, MetacallPointer, MetacallThrowable, MetacallValue,
+};
+use crate::parsers;
+
+// Used for documentation.
+#[allow(unused_imports)]
+use crate::match_metacall_value;
+
+#[derive(Debug, Clone)]
+/// Represents any Metacall value. Unlike the trait objects returned by the untyped functions, it
+/// can be matched natively, nested arrays and maps included. It can be used as the return type
+/// of any call or converted from an untyped result instead of using
+/// [match_metacall_value](match_metacall_value). Usage example: ...
+/// ```
+/// use metacall::MetacallAny;
+///
+/// match metacall::metacall_no_arg::<MetacallAny>("get_users").unwrap() {
+///     MetacallAny::Array(users) => {
+///         for user in users {
+///             if let MetacallAny::Map(fields) = user {
+///                 println!("{:?}", fields.get(&MetacallAny::String(String::from("name"))));
+///             }
+///         }
+///     }
+///     MetacallAny::Null => println!("No users!"),
+///     other => println!("Unexpected value: {:?}", other),
+/// }
+///
+/// // Or from an untyped result
+/// let value: MetacallAny = metacall::metacall_untyped_no_arg("get_users").unwrap().into();
+/// ```
+pub enum MetacallAny {
+    Bool(bool),
+    Char(char),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    Buffer(MetacallBuffer),
+    Array(Vec<MetacallAny>),
+    Map(MetacallMap<MetacallAny, MetacallAny>),
+    Pointer(MetacallPointer),
+    Future(MetacallFuture),
+    Function(MetacallFunction),
+    Null,
+    Class(MetacallClass),
+    Object(MetacallObject),
+    Exception(MetacallException),
+    Throwable(MetacallThrowable),
+}
+impl PartialEq for MetacallAny {
+    /// Compares the values of plain data. Functions, classes and other references to the loaded
+    /// scripts are never equal.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Bool(a), Self::Bool(b)) => a == b,
+            (Self::Char(a), Self::Char(b)) => a == b,
+            (Self::Short(a), Self::Short(b)) => a == b,
+            (Self::Int(a), Self::Int(b)) => a == b,
+            (Self::Long(a), Self::Long(b)) => a == b,
+            (Self::Float(a), Self::Float(b)) => a == b,
+            (Self::Double(a), Self::Double(b)) => a == b,
+            (Self::String(a), Self::String(b)) => a == b,
+            (Self::Buffer(a), Self::Buffer(b)) => a == b,
+            (Self::Array(a), Self::Array(b)) => a == b,
+            (Self::Map(a), Self::Map(b)) => a == b,
+            (Self::Null, Self::Null) => true,
+            _ => false,
+        }
+    }
+}
+
+impl MetacallAny {
+    /// Checks if the value is null.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+}
+
+impl From<Box<dyn MetacallValue>> for MetacallAny {
+    fn from(value: Box<dyn MetacallValue>) -> Self {
+        macro_rules! downcast {
+            ($value:ident, { $( $type:ty => $variant:expr ),* }) => {
+                $(
+                    let $value = match $value.downcast::<$type>() {
+                        Ok(inner) => return ($variant)(inner),
+                        Err(original) => original,
+                    };
+                )*
+            };
+        }
+
+        downcast!(value, {
+            MetacallAny => |any| any,
+            bool => Self::Bool,
+            char => Self::Char,
+            i16 => Self::Short,
+            i32 => Self::Int,
+            i64 => Self::Long,
+            f32 => Self::Float,
+            f64 => Self::Double,
+            String => Self::String,
+            MetacallBuffer => Self::Buffer,
+            Vec<MetacallAny> => Self::Array,
+            MetacallMap<MetacallAny, MetacallAny> => Self::Map,
+            MetacallPointer => Self::Pointer,
+            MetacallFuture => Self::Future,
+            MetacallFunction => Self::Function,
+            MetacallNull => |_| Self::Null,
+            MetacallClass => Self::Class,
+            MetacallObject => Self::Object,
+            MetacallException => Self::Exception,
+            MetacallThrowable => Self::Throwable
+        });
+
+        // Any other type (a HashMap or a serde struct for example) goes through Metacall
+        // to get parsed as one of the types above
+        Self::from(parsers::raw_to_metacallobj_untyped(
+            value.into_metacall_raw_box(),
+        ))
+    }
+}
@@ -0,0 +1,104 @@
+use super::MetacallValue;
+use std::ffi::c_void;
+
+// Used for documentation.
+#[allow(unused_imports)]
+use crate::metacall_args;
+
+/// Trait of the argument lists accepted by calls. It's implemented for arrays, slices and vectors
+/// of a single [MetacallValue](MetacallValue) type, and for tuples of up to 12 elements of
+/// different types. Use `()` for passing no arguments. Other iterators and collections can be
+/// wrapped in [MetacallArgsIter](MetacallArgsIter). Usage example: ...
+/// ```
+/// use metacall::{metacall, metacall_args, MetacallArgsIter, MetacallValue};
+/// use std::collections::HashSet;
+///
+/// // Arguments of the same type
+/// metacall::<i32>("sum", [1, 2]).unwrap();
+///
+/// // Arguments of different types
+/// metacall::<String>("format", (1, String::from("a"), true)).unwrap();
+///
+/// // Arguments of different types built at runtime
+/// let mut args: Vec<Box<dyn MetacallValue>> = metacall_args![1, String::from("a")];
+/// args.push(Box::new(true));
+/// metacall::<String>("format", args).unwrap();
+///
+/// // Arguments from an iterator or any other collection
+/// let numbers = vec![1, 2];
+/// metacall::<i32>("sum", MetacallArgsIter(numbers.iter().map(|n| n * 2))).unwrap();
+/// metacall::<i32>("sum", MetacallArgsIter(HashSet::from([1, 2]))).unwrap();
+/// ```
+pub trait MetacallArgs {
+    // It converts the arguments to raw values known by the metacall core.
+    #[doc(hidden)]
+    fn into_metacall_raw_args(self) -> Vec<*mut c_void>;
+}
+
+impl MetacallArgs for () {
+    fn into_metacall_raw_args(self) -> Vec<*mut c_void> {
+        Vec::new()
+    }
+}
+impl<T: MetacallValue, const N: usize> MetacallArgs for [T; N] {
+    fn into_metacall_raw_args(self) -> Vec<*mut c_void> {
+        self.into_iter()
+            .map(|arg| arg.into_metacall_raw())
+            .collect()
+    }
+}
+impl<T: MetacallValue> MetacallArgs for Vec<T> {
+    fn into_metacall_raw_args(self) -> Vec<*mut c_void> {
+        self.into_iter()
+            .map(|arg| arg.into_metacall_raw())
+            .collect()
+    }
+}
+impl<T: MetacallValue + Clone> MetacallArgs for &[T] {
+    fn into_metacall_raw_args(self) -> Vec<*mut c_void> {
+        self.iter()
+            .map(|arg| arg.clone().into_metacall_raw())
+            .collect()
+    }
+}
+
+/// Wrapper for passing any iterator or collection as the arguments of a call. Checkout
+/// [MetacallArgs](MetacallArgs) for usage.
+#[derive(Debug, Clone)]
+pub struct MetacallArgsIter<I>(pub I);
+impl<I: IntoIterator> MetacallArgs for MetacallArgsIter<I>
+where
+    I::Item: MetacallValue,
+{
+    fn into_metacall_raw_args(self) -> Vec<*mut c_void> {
+        self.0
+            .into_iter()
+            .map(|arg| arg.into_metacall_raw())
+            .collect()
+    }
+}
+
+macro_rules! tuple_args {
+    ($($arg:ident),+) => {
+        impl<$($arg: MetacallValue),+> MetacallArgs for ($($arg,)+) {
+            #[allow(non_snake_case)]
+            fn into_metacall_raw_args(self) -> Vec<*mut c_void> {
+                let ($($arg,)+) = self;
+
+                vec![$($arg.into_metacall_raw()),+]
+            }
+        }
+    };
+}
+tuple_args!(A);
+tuple_args!(A, B);
+tuple_args!(A, B, C);
+tuple_args!(A, B, C, D);
+tuple_args!(A, B, C, D, E);
+tuple_args!(A, B, C, D, E, F);
+tuple_args!(A, B, C, D, E, F, G);
+tuple_args!(A, B, C, D, E, F, G, H);
+tuple_args!(A, B, C, D, E, F, G, H, I);
+tuple_args!(A, B, C, D, E, F, G, H, I, J);
+tuple_args!(A, B, C, D, E, F, G, H, I, J, K);
+tuple_args!(A, B, C, D, E, F, G, H, I, J, K, L);
@@ -0,0 +1,187 @@
+use crate::bindings::{
+    metacall_value_copy, metacall_value_create_buffer, metacall_value_destroy, metacall_value_size,
+    metacall_value_to_buffer,
+};
+use std::{
+    ffi::c_void,
+    fmt::{self, Debug, Formatter},
+    ops::{Deref, DerefMut},
+    ptr, slice,
+};
+
+/// Represents Metacall buffer. The bytes live inside the Metacall value, so reading a buffer
+/// returned by a function (a Python `bytes` or a NodeJS `Buffer` for example) borrows them
+/// without copying. Creating a buffer copies the bytes once into Metacall, or none at all when
+/// it's created with [zeroed](#method.zeroed) and filled in place. Usage example: ...
+/// ```
+/// let image = std::fs::read("image.png").unwrap();
+/// let buffer = metacall::MetacallBuffer::from(image);
+///
+/// let thumbnail = metacall::metacall::<metacall::MetacallBuffer>("thumbnail", [buffer]).unwrap();
+/// // No copy, the slice points to the memory of the returned value
+/// let bytes: &[u8] = &thumbnail;
+/// ```
+/// Buffers are received as `bytes` in Python, so NumPy arrays can be built over them and turned
+/// back into buffers. For example: ...
+/// ```python
+/// import numpy
+///
+/// def thumbnail(buffer):
+///     image = numpy.frombuffer(buffer, dtype=numpy.uint8)
+///     return image[::4].tobytes()
+/// ```
+pub struct MetacallBuffer {
+    leak: bool,
+    value: *mut c_void,
+}
+impl Clone for MetacallBuffer {
+    fn clone(&self) -> Self {
+        Self {
+            leak: false,
+            value: unsafe { metacall_value_copy(self.value) },
+        }
+    }
+}
+impl Debug for MetacallBuffer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetacallBuffer")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl MetacallBuffer {
+    #[doc(hidden)]
+    pub fn new_raw(value: *mut c_void) -> Self {
+        Self { leak: false, value }
+    }
+
+    #[doc(hidden)]
+    pub fn new_raw_leak(value: *mut c_void) -> Self {
+        Self { leak: true, value }
+    }
+
+    /// Creates a new Metacall buffer by copying the bytes.
+    pub fn new(bytes: impl AsRef<[u8]>) -> Self {
+        let bytes = bytes.as_ref();
+
+        Self::new_raw(unsafe { metacall_value_create_buffer(bytes.as_ptr().cast(), bytes.len()) })
+    }
+    /// Creates a new Metacall buffer of `len` bytes set to zero. It can be filled in place
+    /// through [as_mut_slice](#method.as_mut_slice) to avoid copying the data.
+    pub fn zeroed(len: usize) -> Self {
+        Self::new_raw(unsafe { metacall_value_create_buffer(ptr::null(), len) })
+    }
+
+    /// Gets the length of the buffer in bytes.
+    pub fn len(&self) -> usize {
+        unsafe { metacall_value_size(self.value) }
+    }
+    /// Checks if the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Borrows the bytes of the buffer without copying them.
+    pub fn as_slice(&self) -> &[u8] {
+        let len = self.len();
+
+        if len == 0 {
+            return &[];
+        }
+
+        unsafe { slice::from_raw_parts(metacall_value_to_buffer(self.value).cast(), len) }
+    }
+    /// Mutably borrows the bytes of the buffer without copying them.
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        let len = self.len();
+
+        if len == 0 {
+            return &mut [];
+        }
+
+        unsafe { slice::from_raw_parts_mut(metacall_value_to_buffer(self.value).cast(), len) }
+    }
+    /// Copies the bytes of the buffer into a vector.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.as_slice().to_vec()
+    }
+    #[cfg(feature = "bytes")]
+    /// Copies the bytes of the buffer into [Bytes](bytes::Bytes).
+    pub fn to_bytes(&self) -> bytes::Bytes {
+        bytes::Bytes::copy_from_slice(self.as_slice())
+    }
+
+    #[doc(hidden)]
+    pub fn into_raw(mut self) -> *mut c_void {
+        // Borrowed buffers are owned by someone else, so they get copied before being handed out
+        let value = if self.leak {
+            unsafe { metacall_value_copy(self.value) }
+        } else {
+            self.value
+        };
+        self.leak = true;
+
+        value
+    }
+}
+
+impl Deref for MetacallBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+impl DerefMut for MetacallBuffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+impl AsRef<[u8]> for MetacallBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+impl PartialEq for MetacallBuffer {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+impl Eq for MetacallBuffer {}
+
+impl From<&[u8]> for MetacallBuffer {
+    fn from(bytes: &[u8]) -> Self {
+        Self::new(bytes)
+    }
+}
+impl From<Vec<u8>> for MetacallBuffer {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::new(bytes)
+    }
+}
+#[cfg(feature = "bytes")]
+impl From<bytes::Bytes> for MetacallBuffer {
+    fn from(bytes: bytes::Bytes) -> Self {
+        Self::new(bytes)
+    }
+}
+impl From<MetacallBuffer> for Vec<u8> {
+    fn from(buffer: MetacallBuffer) -> Self {
+        buffer.to_vec()
+    }
+}
+#[cfg(feature = "bytes")]
+impl From<MetacallBuffer> for bytes::Bytes {
+    fn from(buffer: MetacallBuffer) -> Self {
+        buffer.to_bytes()
+    }
+}
+
+impl Drop for MetacallBuffer {
+    fn drop(&mut self) {
+        if !self.leak {
+            unsafe { metacall_value_destroy(self.value) }
+        }
+    }
+}
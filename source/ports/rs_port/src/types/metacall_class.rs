@@ -1,26 +1,28 @@
 use super::{
-    MetacallClassFromNameError, MetacallError, MetacallGetAttributeError, MetacallNull,
-    MetacallObject, MetacallSetAttributeError, MetacallStringConversionError, MetacallValue,
+    MetacallArgs, MetacallClassFromNameError, MetacallError, MetacallFuture,
+    MetacallGetAttributeError, MetacallObject, MetacallSetAttributeError, MetacallValue,
 };
-use crate::{bindings::*, cstring, cstring_enum, parsers};
+use crate::{bindings::*, check_initialized, cstring_enum, parsers, switch::MetacallGeneration};
 use std::{
     ffi::c_void,
     fmt::{self, Debug, Formatter},
+    future::Future,
 };
 
 /// Represents Metacall Class. You can get this type when returned by a function or get a class by its
-/// name with [from_name](#method.from_name).
+/// name with [from_name](#method.from_name). Classes can't be used anymore once Metacall is
+/// destroyed, using them returns a `NotInitialized` error.
 pub struct MetacallClass {
     found_by_name: bool,
+    generation: MetacallGeneration,
     leak: bool,
     value: *mut c_void,
 }
-unsafe impl Send for MetacallClass {}
-unsafe impl Sync for MetacallClass {}
 impl Clone for MetacallClass {
     fn clone(&self) -> Self {
         Self {
             found_by_name: self.found_by_name,
+            generation: self.generation,
             leak: true,
             value: self.value,
         }
@@ -37,6 +39,7 @@ impl MetacallClass {
     pub fn new_raw(value: *mut c_void) -> Self {
         Self {
             found_by_name: false,
+            generation: MetacallGeneration::current(),
             leak: false,
             value,
         }
@@ -46,6 +49,7 @@ impl MetacallClass {
     pub fn new_raw_leak(value: *mut c_void) -> Self {
         Self {
             found_by_name: false,
+            generation: MetacallGeneration::current(),
             leak: true,
             value,
         }
@@ -53,6 +57,7 @@ impl MetacallClass {
 
     /// Gets a class by its name.
     pub fn from_name(name: impl ToString) -> Result<Self, MetacallClassFromNameError> {
+        check_initialized!(MetacallClassFromNameError);
         let c_name = cstring_enum!(name, MetacallClassFromNameError)?;
         let class = unsafe { metacall_class(c_name.as_ptr()) };
 
@@ -62,6 +67,7 @@ impl MetacallClass {
 
         Ok(Self {
             found_by_name: true,
+            generation: MetacallGeneration::current(),
             leak: true,
             value: class,
         })
@@ -76,13 +82,15 @@ impl MetacallClass {
     }
 
     /// Creates an [object](MetacallObject) of the class wtih constructor arguments.
-    pub fn create_object<T: MetacallValue>(
+    pub fn create_object(
         &self,
         name: impl ToString,
-        constructor_args: impl IntoIterator<Item = T>,
-    ) -> Result<MetacallObject, MetacallStringConversionError> {
-        let c_name = cstring!(name)?;
-        let mut c_args = parsers::metacallobj_to_raw_args(constructor_args);
+        constructor_args: impl MetacallArgs,
+    ) -> Result<MetacallObject, MetacallError> {
+        check_initialized!(self.generation, MetacallError);
+        let c_name = cstring_enum!(name, MetacallError)?;
+        let mut c_args = parsers::metacallobj_to_raw_args(constructor_args)
+            .map_err(MetacallError::FailedConversion)?;
         let obj = unsafe {
             metacall_class_new(
                 self.value_to_class(),
@@ -102,14 +110,15 @@ impl MetacallClass {
     pub fn create_object_no_arg(
         &self,
         name: impl ToString,
-    ) -> Result<MetacallObject, MetacallStringConversionError> {
-        self.create_object::<MetacallNull>(name, [])
+    ) -> Result<MetacallObject, MetacallError> {
+        self.create_object(name, ())
     }
 
     fn get_attribute_inner(
         &self,
         name: impl ToString,
     ) -> Result<*mut c_void, MetacallGetAttributeError> {
+        check_initialized!(self.generation, MetacallGetAttributeError);
         let c_name = cstring_enum!(name, MetacallGetAttributeError)?;
 
         Ok(unsafe { metacall_class_static_get(self.value_to_class(), c_name.as_ptr()) })
@@ -140,9 +149,11 @@ impl MetacallClass {
         key: impl ToString,
         value: impl MetacallValue,
     ) -> Result<(), MetacallSetAttributeError> {
+        check_initialized!(self.generation, MetacallSetAttributeError);
         let c_key = cstring_enum!(key, MetacallSetAttributeError)?;
 
-        let c_arg = parsers::metacallobj_to_raw(value);
+        let c_arg = parsers::metacallobj_to_raw(value)
+            .map_err(MetacallSetAttributeError::FailedConversion)?;
         if unsafe { metacall_class_static_set(self.value_to_class(), c_key.as_ptr(), c_arg) } != 0 {
             return Err(MetacallSetAttributeError::SetAttributeFailure);
         }
@@ -152,13 +163,15 @@ impl MetacallClass {
         Ok(())
     }
 
-    fn call_method_inner<T: MetacallValue>(
+    fn call_method_inner(
         &self,
         name: impl ToString,
-        args: impl IntoIterator<Item = T>,
+        args: impl MetacallArgs,
     ) -> Result<*mut c_void, MetacallError> {
+        check_initialized!(self.generation, MetacallError);
         let c_key = cstring_enum!(name, MetacallError)?;
-        let mut c_args = parsers::metacallobj_to_raw_args(args);
+        let mut c_args =
+            parsers::metacallobj_to_raw_args(args).map_err(MetacallError::FailedConversion)?;
         let ret = unsafe {
             metacallv_class(
                 self.value_to_class(),
@@ -175,34 +188,34 @@ impl MetacallClass {
         Ok(ret)
     }
     /// Calls a static class method witout type casting([MetacallValue](MetacallValue)).
-    pub fn call_method_untyped<T: MetacallValue>(
+    pub fn call_method_untyped(
         &self,
         name: impl ToString,
-        args: impl IntoIterator<Item = T>,
+        args: impl MetacallArgs,
     ) -> Result<Box<dyn MetacallValue>, MetacallError> {
         Ok(parsers::raw_to_metacallobj_untyped(
-            self.call_method_inner::<T>(name, args)?,
+            self.call_method_inner(name, args)?,
         ))
     }
     /// Calls a static class method witout type casting([MetacallValue](MetacallValue)) and
     /// without passing arguments.
-    pub fn call_method_untyped_no_arg<T: MetacallValue>(
+    pub fn call_method_untyped_no_arg(
         &self,
         name: impl ToString,
     ) -> Result<Box<dyn MetacallValue>, MetacallError> {
         Ok(parsers::raw_to_metacallobj_untyped(
-            self.call_method_inner::<T>(name, [])?,
+            self.call_method_inner(name, ())?,
         ))
     }
     /// Calls a static class method.
-    pub fn call_method<T: MetacallValue, U: MetacallValue>(
+    pub fn call_method<T: MetacallValue>(
         &self,
         name: impl ToString,
-        args: impl IntoIterator<Item = U>,
+        args: impl MetacallArgs,
     ) -> Result<T, MetacallError> {
-        match parsers::raw_to_metacallobj::<T>(self.call_method_inner::<U>(name, args)?) {
+        match parsers::raw_to_metacallobj::<T>(self.call_method_inner(name, args)?) {
             Ok(ret) => Ok(ret),
-            Err(original) => Err(MetacallError::FailedCasting(original)),
+            Err(original) => Err(MetacallError::from_failed_casting(original)),
         }
     }
     /// Calls a static class method without passing arguments.
@@ -210,7 +223,33 @@ impl MetacallClass {
         &self,
         name: impl ToString,
     ) -> Result<T, MetacallError> {
-        self.call_method::<T, MetacallNull>(name, [])
+        self.call_method::<T>(name, ())
+    }
+    /// Calls a static class method and awaits it natively if it returns a future, so asynchronous
+    /// methods can be awaited the same way as [metacall_async](crate::metacall_async). The method
+    /// is called right away. Usage example: ...
+    /// ```
+    /// async fn handler(class: metacall::MetacallClass) {
+    ///     let user = class
+    ///         .call_method_async::<String>("fetch_user", [1])
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn call_method_async<T: MetacallValue>(
+        &self,
+        name: impl ToString,
+        args: impl MetacallArgs,
+    ) -> impl Future<Output = Result<T, MetacallError>> {
+        MetacallFuture::await_returned(self.call_method_inner(name, args))
+    }
+    /// Calls a static class method without passing arguments and awaits it natively if it
+    /// returns a future.
+    pub fn call_method_async_no_arg<T: MetacallValue>(
+        &self,
+        name: impl ToString,
+    ) -> impl Future<Output = Result<T, MetacallError>> {
+        self.call_method_async::<T>(name, ())
     }
 
     #[doc(hidden)]
@@ -223,7 +262,8 @@ impl MetacallClass {
 
 impl Drop for MetacallClass {
     fn drop(&mut self) {
-        if !self.leak {
+        // The value was freed along with the runtime
+        if !self.leak && self.generation.is_alive() {
             unsafe { metacall_value_destroy(self.value) }
         }
     }
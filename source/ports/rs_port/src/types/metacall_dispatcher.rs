@@ -0,0 +1,505 @@
+use super::{MetacallArgs, MetacallDispatcherError, MetacallInitError, MetacallValue};
+use crate::{
+    metacall, metacall_async,
+    switch::{self, MetacallAutoDestroy},
+};
+use std::{
+    cell::RefCell,
+    fmt::{self, Debug, Formatter},
+    future::Future,
+    mem,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{
+        mpsc::{self, Sender},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, JoinHandle, ThreadId},
+};
+
+// Work sent to the dispatcher thread.
+type MetacallJob = Box<dyn FnOnce() + Send>;
+// Future driven by the dispatcher thread.
+type MetacallLocalFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+enum MetacallMessage {
+    Job(MetacallJob),
+    Stop,
+}
+
+thread_local! {
+    // Channel of the dispatcher running in the current thread, if any.
+    static DISPATCHER: RefCell<Option<Sender<MetacallMessage>>> = const { RefCell::new(None) };
+    // Futures being driven by the dispatcher running in the current thread.
+    static TASKS: RefCell<Vec<Arc<MetacallTask>>> = const { RefCell::new(Vec::new()) };
+}
+
+// Moves a value that's only going to be used by the dispatcher thread.
+struct AssertSend<T>(T);
+unsafe impl<T> Send for AssertSend<T> {}
+
+// Future of the dispatcher thread, which is polled again in it every time it gets woken.
+struct MetacallTask {
+    future: Mutex<Option<AssertSend<MetacallLocalFuture>>>,
+    sender: Sender<MetacallMessage>,
+}
+impl MetacallTask {
+    fn spawn(future: impl Future<Output = ()> + 'static) {
+        let sender = DISPATCHER
+            .with(|dispatcher| dispatcher.borrow().clone())
+            .expect("Futures can only be spawned in the dispatcher thread!");
+        let task = Arc::new(Self {
+            future: Mutex::new(Some(AssertSend(Box::pin(future)))),
+            sender,
+        });
+
+        TASKS.with(|tasks| tasks.borrow_mut().push(task.clone()));
+        task.poll();
+    }
+
+    fn poll(self: Arc<Self>) {
+        let mut future = self.future.lock().unwrap();
+        let Some(AssertSend(inner)) = future.as_mut() else {
+            return;
+        };
+
+        let waker = Waker::from(self.clone());
+        // A panic would stop the dispatcher thread, so the future is dropped instead
+        let poll = panic::catch_unwind(AssertUnwindSafe(|| {
+            inner.as_mut().poll(&mut Context::from_waker(&waker))
+        }));
+        if !matches!(poll, Ok(Poll::Pending)) {
+            *future = None;
+            TASKS.with(|tasks| tasks.borrow_mut().retain(|task| !Arc::ptr_eq(task, &self)));
+        }
+    }
+
+    // Drops the futures left in the dispatcher thread, so their callers get notified.
+    fn cancel_all() {
+        for task in TASKS.with(|tasks| mem::take(&mut *tasks.borrow_mut())) {
+            task.future.lock().unwrap().take();
+        }
+    }
+}
+impl Wake for MetacallTask {
+    fn wake(self: Arc<Self>) {
+        let task = self.clone();
+
+        if let Err(err) = self
+            .sender
+            .send(MetacallMessage::Job(Box::new(move || task.poll())))
+        {
+            // The dispatcher is stopped, and the future can't be dropped in this thread
+            mem::forget(err);
+        }
+    }
+}
+
+// Result of a job sent back from the dispatcher thread to an asynchronous caller.
+struct MetacallOneshot<T> {
+    closed: bool,
+    result: Option<Result<T, MetacallDispatcherError>>,
+    waker: Option<Waker>,
+}
+struct MetacallOneshotSender<T>(Arc<Mutex<MetacallOneshot<T>>>);
+impl<T> MetacallOneshotSender<T> {
+    fn send(self, result: Result<T, MetacallDispatcherError>) {
+        self.0.lock().unwrap().result = Some(result);
+    }
+}
+impl<T> Drop for MetacallOneshotSender<T> {
+    fn drop(&mut self) {
+        let mut oneshot = self.0.lock().unwrap();
+        oneshot.closed = true;
+
+        if let Some(waker) = oneshot.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Future returned by [call_async](MetacallClient#method.call_async). Unlike the futures of
+/// Metacall, it can be sent between threads, so it can be spawned in any async runtime. It
+/// fails with `Stopped` error variant if the dispatcher is dropped before the call settles, or if
+/// awaiting the call panics in the dispatcher thread.
+pub struct MetacallClientFuture<T> {
+    oneshot: Arc<Mutex<MetacallOneshot<T>>>,
+}
+impl<T> Debug for MetacallClientFuture<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetacallClientFuture")
+            .field("closed", &self.oneshot.lock().unwrap().closed)
+            .finish()
+    }
+}
+impl<T> MetacallClientFuture<T> {
+    fn channel() -> (MetacallOneshotSender<T>, Self) {
+        let oneshot = Arc::new(Mutex::new(MetacallOneshot {
+            closed: false,
+            result: None,
+            waker: None,
+        }));
+
+        (MetacallOneshotSender(oneshot.clone()), Self { oneshot })
+    }
+}
+impl<T> Future for MetacallClientFuture<T> {
+    type Output = Result<T, MetacallDispatcherError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut oneshot = self.oneshot.lock().unwrap();
+
+        if let Some(result) = oneshot.result.take() {
+            return Poll::Ready(result);
+        }
+        // The job was dropped as the dispatcher got stopped before settling it
+        if oneshot.closed {
+            return Poll::Ready(Err(MetacallDispatcherError::Stopped));
+        }
+        oneshot.waker = Some(cx.waker().clone());
+
+        Poll::Pending
+    }
+}
+
+/// Owns the Metacall runtime on a dedicated thread. Runtimes such as NodeJS or Python are not
+/// thread safe, so Metacall values can't be shared between threads. Instead, each thread gets a
+/// [MetacallClient](MetacallClient) that sends the calls over a channel to be run one by one in
+/// the dispatcher thread. Metacall is destroyed when the dispatcher is dropped. Usage example: ...
+/// ```
+/// let dispatcher = metacall::MetacallDispatcher::new().unwrap();
+/// let client = dispatcher.client();
+///
+/// client
+///     .run(|| metacall::loaders::from_single_file("node", "sum.js"))
+///     .unwrap()
+///     .unwrap();
+///
+/// let workers: Vec<_> = (0..4)
+///     .map(|i| {
+///         let client = client.clone();
+///
+///         std::thread::spawn(move || client.call::<f64>("sum", [i as f64, 1.0]).unwrap())
+///     })
+///     .collect();
+///
+/// for worker in workers {
+///     println!("{}", worker.join().unwrap());
+/// }
+/// ```
+pub struct MetacallDispatcher {
+    client: MetacallClient,
+    thread: Option<JoinHandle<()>>,
+}
+impl Debug for MetacallDispatcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetacallDispatcher")
+            .field("thread", &self.client.thread)
+            .finish()
+    }
+}
+
+impl MetacallDispatcher {
+    /// Starts the dispatcher thread and initializes Metacall in it.
+    pub fn new() -> Result<Self, MetacallInitError> {
+        Self::with_initializer(switch::initialize)
+    }
+    /// Starts the dispatcher thread and initializes Metacall in it with the given function, so
+    /// the runtime can be configured. For example: ...
+    /// ```
+    /// use metacall::{MetacallBuilder, MetacallDispatcher, MetacallLog};
+    ///
+    /// let dispatcher = MetacallDispatcher::with_initializer(|| {
+    ///     MetacallBuilder::new().log(MetacallLog::Stderr).initialize()
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn with_initializer<E: Send + 'static>(
+        initialize: impl FnOnce() -> Result<MetacallAutoDestroy, E> + Send + 'static,
+    ) -> Result<Self, E> {
+        let (sender, receiver) = mpsc::channel();
+        let (initialized_sender, initialized_receiver) = mpsc::channel();
+        let dispatcher_sender = sender.clone();
+
+        let thread = thread::spawn(move || {
+            let metacall = match initialize() {
+                Ok(metacall) => metacall,
+                Err(err) => {
+                    initialized_sender.send(Err(err)).ok();
+
+                    return;
+                }
+            };
+            DISPATCHER.with(|dispatcher| *dispatcher.borrow_mut() = Some(dispatcher_sender));
+            initialized_sender.send(Ok(())).ok();
+
+            for message in receiver {
+                match message {
+                    MetacallMessage::Job(job) => job(),
+                    MetacallMessage::Stop => break,
+                }
+            }
+
+            // The jobs and futures left are dropped along with the channel before destroying the
+            // runtime
+            MetacallTask::cancel_all();
+            DISPATCHER.with(|dispatcher| dispatcher.borrow_mut().take());
+            drop(metacall);
+        });
+
+        let client = MetacallClient {
+            sender,
+            thread: thread.thread().id(),
+        };
+        match initialized_receiver.recv() {
+            Ok(Ok(())) => Ok(Self {
+                client,
+                thread: Some(thread),
+            }),
+            Ok(Err(err)) => {
+                thread.join().ok();
+
+                Err(err)
+            }
+            Err(_) => match thread.join() {
+                Err(payload) => panic::resume_unwind(payload),
+                Ok(()) => unreachable!("The dispatcher thread exited without initializing!"),
+            },
+        }
+    }
+
+    /// Gets a client of the dispatcher, which can be cloned and moved to other threads.
+    pub fn client(&self) -> MetacallClient {
+        self.client.clone()
+    }
+}
+
+impl Drop for MetacallDispatcher {
+    fn drop(&mut self) {
+        self.client.sender.send(MetacallMessage::Stop).ok();
+
+        // Joining from the dispatcher thread itself would never return
+        if thread::current().id() != self.client.thread {
+            if let Some(thread) = self.thread.take() {
+                thread.join().ok();
+            }
+        }
+    }
+}
+
+/// Client of a [MetacallDispatcher](MetacallDispatcher). It runs the calls in the dispatcher
+/// thread and waits for their results, so it can be used from any thread. Calls fail with
+/// `Stopped` error variant once the dispatcher is dropped.
+#[derive(Clone)]
+pub struct MetacallClient {
+    sender: Sender<MetacallMessage>,
+    thread: ThreadId,
+}
+impl Debug for MetacallClient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetacallClient")
+            .field("thread", &self.thread)
+            .finish()
+    }
+}
+
+impl MetacallClient {
+    /// Runs the closure in the dispatcher thread and waits for its result. Values that can't be
+    /// sent between threads, such as objects, can be returned wrapped in
+    /// [MetacallSendable](MetacallSendable). If the closure panics, the panic is propagated to the
+    /// caller. For example: ...
+    /// ```
+    /// use metacall::{MetacallObject, MetacallSendable};
+    ///
+    /// let object = client
+    ///     .run(|| MetacallSendable::new(metacall::metacall_no_arg::<MetacallObject>("new_user").unwrap()))
+    ///     .unwrap();
+    ///
+    /// let name = client
+    ///     .run(move || object.get().unwrap().get_attribute::<String>("name").unwrap())
+    ///     .unwrap();
+    /// ```
+    pub fn run<R: Send + 'static>(
+        &self,
+        job: impl FnOnce() -> R + Send + 'static,
+    ) -> Result<R, MetacallDispatcherError> {
+        // Waiting for the dispatcher thread from itself would never return
+        if thread::current().id() == self.thread {
+            return Ok(job());
+        }
+
+        let (result_sender, result_receiver) = mpsc::channel();
+        let job: MetacallJob = Box::new(move || {
+            result_sender
+                .send(panic::catch_unwind(AssertUnwindSafe(job)))
+                .ok();
+        });
+
+        if self.sender.send(MetacallMessage::Job(job)).is_err() {
+            return Err(MetacallDispatcherError::Stopped);
+        }
+
+        match result_receiver.recv() {
+            Ok(Ok(result)) => Ok(result),
+            Ok(Err(payload)) => panic::resume_unwind(payload),
+            // The job was dropped as the dispatcher got stopped before running it
+            Err(_) => Err(MetacallDispatcherError::Stopped),
+        }
+    }
+
+    /// Calls a function in the dispatcher thread, same as [metacall](crate::metacall).
+    pub fn call<T: MetacallValue + Send>(
+        &self,
+        func: impl ToString,
+        args: impl MetacallArgs + Send + 'static,
+    ) -> Result<T, MetacallDispatcherError> {
+        let func = func.to_string();
+
+        self.run(move || {
+            metacall::<T>(func, args).map_err(|err| MetacallDispatcherError::CallErr {
+                message: err.to_string(),
+                error: MetacallSendable::new(err),
+            })
+        })?
+    }
+    /// Calls a function in the dispatcher thread without passing arguments.
+    pub fn call_no_arg<T: MetacallValue + Send>(
+        &self,
+        func: impl ToString,
+    ) -> Result<T, MetacallDispatcherError> {
+        self.call::<T>(func, ())
+    }
+
+    /// Calls an asynchronous function in the dispatcher thread, same as
+    /// [metacall_async](crate::metacall_async), without waiting for it. The function is called
+    /// right away and awaited in the dispatcher thread, and the returned future can be awaited
+    /// from any thread. For example: ...
+    /// ```
+    /// let user = tokio::spawn(client.call_async::<String>("fetch_user", [1]))
+    ///     .await
+    ///     .unwrap()
+    ///     .unwrap();
+    /// ```
+    pub fn call_async<T: MetacallValue + Send>(
+        &self,
+        func: impl ToString,
+        args: impl MetacallArgs + Send + 'static,
+    ) -> MetacallClientFuture<T> {
+        let func = func.to_string();
+        let (sender, future) = MetacallClientFuture::channel();
+
+        let job: MetacallJob = Box::new(move || {
+            MetacallTask::spawn(async move {
+                let result = metacall_async::<T>(func, args).await.map_err(|err| {
+                    MetacallDispatcherError::CallErr {
+                        message: err.to_string(),
+                        error: MetacallSendable::new(err),
+                    }
+                });
+
+                sender.send(result);
+            })
+        });
+        // The future fails with `Stopped` once the job is dropped
+        self.sender.send(MetacallMessage::Job(job)).ok();
+
+        future
+    }
+    /// Calls an asynchronous function in the dispatcher thread without passing arguments.
+    pub fn call_async_no_arg<T: MetacallValue + Send>(
+        &self,
+        func: impl ToString,
+    ) -> MetacallClientFuture<T> {
+        self.call_async::<T>(func, ())
+    }
+}
+
+/// Wraps a value that can't be sent between threads, such as an
+/// [object](crate::MetacallObject), so it can be moved out of the dispatcher thread and back.
+/// The value can only be accessed in the dispatcher thread, and it's dropped there no matter
+/// which thread drops the wrapper.
+pub struct MetacallSendable<T: 'static> {
+    sender: Sender<MetacallMessage>,
+    thread: ThreadId,
+    value: Option<T>,
+}
+unsafe impl<T: 'static> Send for MetacallSendable<T> {}
+impl<T: 'static> Debug for MetacallSendable<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetacallSendable")
+            .field("thread", &self.thread)
+            .finish()
+    }
+}
+
+impl<T: 'static> MetacallSendable<T> {
+    /// Wraps a value of the dispatcher thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if it's not called in a dispatcher thread.
+    pub fn new(value: T) -> Self {
+        let sender = DISPATCHER
+            .with(|dispatcher| dispatcher.borrow().clone())
+            .expect("Values can only be wrapped in the dispatcher thread!");
+
+        Self {
+            sender,
+            thread: thread::current().id(),
+            value: Some(value),
+        }
+    }
+
+    fn is_owner(&self) -> bool {
+        thread::current().id() == self.thread
+    }
+
+    /// Gets the value. It's `None` outside of the dispatcher thread.
+    pub fn get(&self) -> Option<&T> {
+        if !self.is_owner() {
+            return None;
+        }
+
+        self.value.as_ref()
+    }
+    /// Gets the value mutably. It's `None` outside of the dispatcher thread.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        if !self.is_owner() {
+            return None;
+        }
+
+        self.value.as_mut()
+    }
+    /// Unwraps the value. The wrapper is given back outside of the dispatcher thread.
+    pub fn into_inner(mut self) -> Result<T, Self> {
+        if !self.is_owner() {
+            return Err(self);
+        }
+
+        Ok(self.value.take().unwrap())
+    }
+}
+
+impl<T: 'static> Drop for MetacallSendable<T> {
+    fn drop(&mut self) {
+        let Some(value) = self.value.take() else {
+            return;
+        };
+
+        if self.is_owner() {
+            drop(value);
+
+            return;
+        }
+
+        let value = AssertSend(value);
+        if let Err(err) = self
+            .sender
+            .send(MetacallMessage::Job(Box::new(move || drop(value))))
+        {
+            // The dispatcher is stopped, and the value can't be dropped in this thread
+            mem::forget(err);
+        }
+    }
+}
@@ -1,5 +1,10 @@
-use super::MetacallValue;
-use std::{ffi::NulError, path::PathBuf};
+use super::{MetacallException, MetacallSendable, MetacallThrowable, MetacallValue};
+use std::{
+    error::Error,
+    ffi::NulError,
+    fmt::{self, Display, Formatter},
+    path::PathBuf,
+};
 
 #[derive(Debug, Clone)]
 /// This error happens when it's not possible to initialize the Metacall core. You can check
@@ -16,11 +21,77 @@ impl Default for MetacallInitError {
         MetacallInitError::new()
     }
 }
-impl ToString for MetacallInitError {
-    fn to_string(&self) -> String {
-        String::from("Failed to initialize Metacall!")
+impl Display for MetacallInitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to initialize Metacall!")
     }
 }
+impl Error for MetacallInitError {}
+
+#[derive(Debug, Clone)]
+/// This error may happen when initializing Metacall with [MetacallBuilder](crate::MetacallBuilder).
+/// Check your logs for more information if you get `InitFailure` error variant.
+pub enum MetacallBuilderError {
+    /// Failed to configure the logs.
+    LogFailure,
+    /// Failed to initialize Metacall or one of the loaders given options.
+    InitFailure,
+    /// Metacall is already initialized, so the configuration can't be applied.
+    AlreadyInitialized,
+    /// Failed to add an execution path.
+    ExecutionPathFailure(PathBuf),
+    /// Null character detected.
+    UnexpectedCStringConversionErr(MetacallStringConversionError),
+}
+impl Display for MetacallBuilderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LogFailure => write!(f, "Failed to configure the logs!"),
+            Self::InitFailure => write!(f, "Failed to initialize Metacall!"),
+            Self::AlreadyInitialized => write!(f, "Metacall is already initialized!"),
+            Self::ExecutionPathFailure(path) => {
+                write!(
+                    f,
+                    "Failed to add `{}` as an execution path!",
+                    path.display()
+                )
+            }
+            Self::UnexpectedCStringConversionErr(err) => {
+                write!(f, "Null character detected in `{}`!", err)
+            }
+        }
+    }
+}
+impl Error for MetacallBuilderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::UnexpectedCStringConversionErr(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// This error may happen when forking the process with [fork](crate::fork). Check your logs
+/// for more information if you get `DetourFailure` error variant.
+pub enum MetacallForkError {
+    /// See [`MetacallError::NotInitialized`].
+    NotInitialized,
+    /// Failed to hook the fork of the process.
+    DetourFailure,
+    /// Failed to fork the process.
+    ForkFailure,
+}
+impl Display for MetacallForkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInitialized => write!(f, "Metacall is not initialized!"),
+            Self::DetourFailure => write!(f, "Failed to hook the fork of the process!"),
+            Self::ForkFailure => write!(f, "Failed to fork the process!"),
+        }
+    }
+}
+impl Error for MetacallForkError {}
 
 #[derive(Debug, Clone)]
 /// This error may happen when passing contains a null character. You can access the
@@ -38,63 +109,432 @@ impl MetacallStringConversionError {
         }
     }
 }
-impl ToString for MetacallStringConversionError {
-    fn to_string(&self) -> String {
-        self.original_string.clone()
+impl Display for MetacallStringConversionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.original_string)
+    }
+}
+impl Error for MetacallStringConversionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.nul_error)
     }
 }
 
 #[derive(Debug, Clone)]
-/// This error may happen when trying to call a function.
+/// This error may happen when a Rust value can't be converted into a Metacall value, such as an
+/// integer that doesn't fit into a Metacall long. You can access the reason of the failure
+/// throughout this struct.
+pub struct MetacallConversionError {
+    pub message: String,
+}
+impl MetacallConversionError {
+    #[doc(hidden)]
+    pub fn new(message: impl ToString) -> Self {
+        Self {
+            message: message.to_string(),
+        }
+    }
+}
+impl Display for MetacallConversionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+impl Error for MetacallConversionError {}
+
+#[derive(Debug, Clone)]
+/// This error may happen when trying to call a function. The exception is given in `Thrown`
+/// error variant if the function throws or returns one, unless the type requested accepts it.
 pub enum MetacallError {
+    /// Metacall is not initialized, or the value belongs to a runtime that's already destroyed.
+    NotInitialized,
     /// Function not found.
     FunctionNotFound,
     /// Failed to cast the return type as the type requested.
     FailedCasting(Box<dyn MetacallValue>),
+    /// The function threw or returned an exception.
+    Thrown(MetacallException),
+    /// The awaited function rejected with a value that's not an exception.
+    Rejected(Box<dyn MetacallValue>),
+    /// Failed to await the function. It may not be supported by its loader.
+    AwaitFailure,
+    /// Failed to convert an argument into a Metacall value.
+    FailedConversion(MetacallConversionError),
     /// Null character detected.
     UnexpectedCStringConversionErr(MetacallStringConversionError),
 }
+impl MetacallError {
+    // Creates the error of a return value that failed to be casted.
+    pub(crate) fn from_failed_casting(original: Box<dyn MetacallValue>) -> Self {
+        if original.is::<MetacallException>() {
+            Self::Thrown(original.downcast::<MetacallException>().unwrap())
+        } else if let Some(throwable) = original.downcast_ref::<MetacallThrowable>() {
+            Self::Thrown(throwable.to_exception())
+        } else {
+            Self::FailedCasting(original)
+        }
+    }
+    // Creates the error of an awaited function that got rejected.
+    pub(crate) fn from_rejected(rejected: Box<dyn MetacallValue>) -> Self {
+        match Self::from_failed_casting(rejected) {
+            Self::FailedCasting(original) => Self::Rejected(original),
+            err => err,
+        }
+    }
+}
+impl Display for MetacallError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInitialized => write!(f, "Metacall is not initialized!"),
+            Self::FunctionNotFound => write!(f, "Function not found!"),
+            Self::FailedCasting(original) => {
+                write!(f, "Failed to cast the return value: {:?}", original)
+            }
+            Self::Thrown(exception) => write!(f, "{}", exception),
+            Self::Rejected(original) => write!(f, "Rejected with: {:?}", original),
+            Self::AwaitFailure => write!(f, "Failed to await the function!"),
+            Self::FailedConversion(err) => write!(f, "Failed to convert an argument: {}", err),
+            Self::UnexpectedCStringConversionErr(err) => {
+                write!(f, "Null character detected in `{}`!", err)
+            }
+        }
+    }
+}
+impl Error for MetacallError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::FailedConversion(err) => Some(err),
+            Self::UnexpectedCStringConversionErr(err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 /// This error may happen when trying to set a class/object attribute. Check your logs
 /// if you get `SetAttributeFailure` error variant.
 pub enum MetacallSetAttributeError {
+    /// See [`MetacallError::NotInitialized`].
+    NotInitialized,
     /// Failed to set the attribute.
     SetAttributeFailure,
+    /// Failed to convert the value into a Metacall value.
+    FailedConversion(MetacallConversionError),
     /// Null character detected.
     UnexpectedCStringConversionErr(MetacallStringConversionError),
 }
+impl Display for MetacallSetAttributeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInitialized => write!(f, "Metacall is not initialized!"),
+            Self::SetAttributeFailure => write!(f, "Failed to set the attribute!"),
+            Self::FailedConversion(err) => write!(f, "Failed to convert the value: {}", err),
+            Self::UnexpectedCStringConversionErr(err) => {
+                write!(f, "Null character detected in `{}`!", err)
+            }
+        }
+    }
+}
+impl Error for MetacallSetAttributeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::FailedConversion(err) => Some(err),
+            Self::UnexpectedCStringConversionErr(err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 /// This error may happen when trying to get a class/object attribute.
 pub enum MetacallGetAttributeError {
+    /// See [`MetacallError::NotInitialized`].
+    NotInitialized,
     /// Failed to cast the attribute as the type requested.
     FailedCasting(Box<dyn MetacallValue>),
     /// Null character detected.
     UnexpectedCStringConversionErr(MetacallStringConversionError),
 }
+impl Display for MetacallGetAttributeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInitialized => write!(f, "Metacall is not initialized!"),
+            Self::FailedCasting(original) => {
+                write!(f, "Failed to cast the attribute: {:?}", original)
+            }
+            Self::UnexpectedCStringConversionErr(err) => {
+                write!(f, "Null character detected in `{}`!", err)
+            }
+        }
+    }
+}
+impl Error for MetacallGetAttributeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::UnexpectedCStringConversionErr(err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 /// This error may happen when loading a code. Check your logs for more information if you
-/// get `FromFileFailure` or `FromMemoryFailure` error variant.
+/// get `FromFileFailure`, `FromMemoryFailure`, `FromPackageFailure` or
+/// `FromConfigurationFailure` error variant.
 pub enum MetacallLoaderError {
+    /// See [`MetacallError::NotInitialized`].
+    NotInitialized,
     /// File not found.
     FileNotFound(PathBuf),
     /// Failed to load from file.
     FromFileFailure,
     /// Failed to load from memory.
     FromMemoryFailure,
+    /// Failed to load from package.
+    FromPackageFailure,
+    /// Failed to load from configuration.
+    FromConfigurationFailure,
     /// Not a file or permission denied.
     NotAFileOrPermissionDenied(PathBuf),
     /// Null character detected.
     UnexpectedCStringConversionErr(MetacallStringConversionError),
 }
+impl Display for MetacallLoaderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInitialized => write!(f, "Metacall is not initialized!"),
+            Self::FileNotFound(path) => write!(f, "File `{}` not found!", path.display()),
+            Self::FromFileFailure => write!(f, "Failed to load from file!"),
+            Self::FromMemoryFailure => write!(f, "Failed to load from memory!"),
+            Self::FromPackageFailure => write!(f, "Failed to load from package!"),
+            Self::FromConfigurationFailure => write!(f, "Failed to load from configuration!"),
+            Self::NotAFileOrPermissionDenied(path) => write!(
+                f,
+                "`{}` is not a file or permission denied!",
+                path.display()
+            ),
+            Self::UnexpectedCStringConversionErr(err) => {
+                write!(f, "Null character detected in `{}`!", err)
+            }
+        }
+    }
+}
+impl Error for MetacallLoaderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::UnexpectedCStringConversionErr(err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 /// This error may happen when trying to get a class by its name.
 pub enum MetacallClassFromNameError {
+    /// See [`MetacallError::NotInitialized`].
+    NotInitialized,
     /// Class not found.
     ClassNotFound,
     /// Null character detected.
     UnexpectedCStringConversionErr(MetacallStringConversionError),
 }
+impl Display for MetacallClassFromNameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInitialized => write!(f, "Metacall is not initialized!"),
+            Self::ClassNotFound => write!(f, "Class not found!"),
+            Self::UnexpectedCStringConversionErr(err) => {
+                write!(f, "Null character detected in `{}`!", err)
+            }
+        }
+    }
+}
+impl Error for MetacallClassFromNameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::UnexpectedCStringConversionErr(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// This error may happen when registering a Rust closure as a Metacall function. Check your
+/// logs for more information if you get `RegisterFailure` error variant.
+pub enum MetacallRegisterError {
+    /// See [`MetacallError::NotInitialized`].
+    NotInitialized,
+    /// Failed to register the function.
+    RegisterFailure,
+    /// Null character detected.
+    UnexpectedCStringConversionErr(MetacallStringConversionError),
+}
+impl Display for MetacallRegisterError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInitialized => write!(f, "Metacall is not initialized!"),
+            Self::RegisterFailure => write!(f, "Failed to register the function!"),
+            Self::UnexpectedCStringConversionErr(err) => {
+                write!(f, "Null character detected in `{}`!", err)
+            }
+        }
+    }
+}
+impl Error for MetacallRegisterError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::UnexpectedCStringConversionErr(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// This error may happen when calling a function declared with [bind](crate::bind).
+pub enum MetacallBindError {
+    /// Failed to load the script of the function.
+    LoadingErr(MetacallLoaderError),
+    /// Failed to call the function.
+    CallErr(MetacallError),
+}
+impl Display for MetacallBindError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LoadingErr(err) => write!(f, "Failed to load the script: {}", err),
+            Self::CallErr(err) => write!(f, "Failed to call the function: {}", err),
+        }
+    }
+}
+impl Error for MetacallBindError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::LoadingErr(err) => Some(err),
+            Self::CallErr(err) => Some(err),
+        }
+    }
+}
+
+#[derive(Debug)]
+/// This error may happen when running a call through a [MetacallClient](crate::MetacallClient).
+pub enum MetacallDispatcherError {
+    /// The dispatcher is stopped.
+    Stopped,
+    /// Failed to call the function. The error can only be accessed in the dispatcher thread, so
+    /// its message is given along with it.
+    CallErr {
+        message: String,
+        error: MetacallSendable<MetacallError>,
+    },
+}
+impl Display for MetacallDispatcherError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stopped => write!(f, "The dispatcher is stopped!"),
+            Self::CallErr { message, .. } => write!(f, "Failed to call the function: {}", message),
+        }
+    }
+}
+impl Error for MetacallDispatcherError {}
+
+#[derive(Debug, Clone)]
+/// This error may happen when inspecting the loaded scripts. Check your logs for more
+/// information.
+pub enum MetacallInspectError {
+    /// See [`MetacallError::NotInitialized`].
+    NotInitialized,
+    /// Failed to inspect the loaded scripts.
+    InspectFailure,
+    /// Failed to deserialize the inspect output.
+    DeserializeFailure,
+}
+impl Display for MetacallInspectError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInitialized => write!(f, "Metacall is not initialized!"),
+            Self::InspectFailure => write!(f, "Failed to inspect the loaded scripts!"),
+            Self::DeserializeFailure => write!(f, "Failed to deserialize the inspect output!"),
+        }
+    }
+}
+impl Error for MetacallInspectError {}
+
+#[derive(Debug, Clone)]
+/// This error may happen when serializing or deserializing a value with a Metacall serializer.
+/// Check your logs for more information if you get `SerializeFailure` or `DeserializeFailure`
+/// error variant.
+pub enum MetacallSerialError {
+    /// Metacall is not initialized.
+    NotInitialized,
+    /// Failed to serialize the value. The serializer may not exist.
+    SerializeFailure,
+    /// Failed to deserialize the buffer. The serializer may not exist or the buffer is invalid.
+    DeserializeFailure,
+    /// Failed to cast the deserialized value as the type requested.
+    FailedCasting(Box<dyn MetacallValue>),
+    /// Failed to convert the value into a Metacall value before serializing it.
+    FailedConversion(MetacallConversionError),
+    /// Null character detected.
+    UnexpectedCStringConversionErr(MetacallStringConversionError),
+}
+impl Display for MetacallSerialError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInitialized => write!(f, "Metacall is not initialized!"),
+            Self::SerializeFailure => write!(f, "Failed to serialize the value!"),
+            Self::DeserializeFailure => write!(f, "Failed to deserialize the buffer!"),
+            Self::FailedCasting(original) => {
+                write!(f, "Failed to cast the deserialized value: {:?}", original)
+            }
+            Self::FailedConversion(err) => write!(f, "Failed to convert the value: {}", err),
+            Self::UnexpectedCStringConversionErr(err) => {
+                write!(f, "Null character detected in `{}`!", err)
+            }
+        }
+    }
+}
+impl Error for MetacallSerialError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::FailedConversion(err) => Some(err),
+            Self::UnexpectedCStringConversionErr(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone)]
+/// This error may happen when serializing or deserializing a value with serde. You can access
+/// the reason of the failure throughout this struct.
+pub struct MetacallSerdeError {
+    pub message: String,
+}
+#[cfg(feature = "serde")]
+impl MetacallSerdeError {
+    #[doc(hidden)]
+    pub fn new(message: impl ToString) -> Self {
+        Self {
+            message: message.to_string(),
+        }
+    }
+}
+#[cfg(feature = "serde")]
+impl Display for MetacallSerdeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+#[cfg(feature = "serde")]
+impl Error for MetacallSerdeError {}
+#[cfg(feature = "serde")]
+impl serde::ser::Error for MetacallSerdeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::new(msg)
+    }
+}
+#[cfg(feature = "serde")]
+impl serde::de::Error for MetacallSerdeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::new(msg)
+    }
+}
@@ -1,14 +1,15 @@
 use super::{MetacallStringConversionError, MetacallValue};
 use crate::{
     bindings::{
-        metacall_exception_type, metacall_throwable_value, metacall_value_create_exception,
-        metacall_value_destroy, metacall_value_to_exception, metacall_value_to_throwable,
+        metacall_exception_type, metacall_throwable_value, metacall_value_copy,
+        metacall_value_create_exception, metacall_value_destroy, metacall_value_to_exception,
+        metacall_value_to_throwable,
     },
-    cstring, helpers, parsers,
+    cstring, parsers,
 };
 use std::{
     ffi::{c_char, c_void, CStr},
-    fmt::{self, Debug, Formatter},
+    fmt::{self, Debug, Display, Formatter},
     sync::Arc,
 };
 
@@ -18,8 +19,6 @@ pub struct MetacallException {
     leak: bool,
     value: *mut c_void,
 }
-unsafe impl Send for MetacallException {}
-unsafe impl Sync for MetacallException {}
 impl Clone for MetacallException {
     fn clone(&self) -> Self {
         Self {
@@ -133,8 +132,6 @@ pub struct MetacallThrowable {
     value_ptr: *mut c_void,
     value: *mut c_void,
 }
-unsafe impl Send for MetacallThrowable {}
-unsafe impl Sync for MetacallThrowable {}
 impl Clone for MetacallThrowable {
     fn clone(&self) -> Self {
         Self {
@@ -179,14 +176,8 @@ impl MetacallThrowable {
 
     /// Gets the throwable value without type casting([MetacallValue](MetacallValue)).
     pub fn get_value_untyped(&self) -> Box<dyn MetacallValue> {
-        match parsers::raw_to_metacallobj::<MetacallException>(self.value) {
-            Ok(mut value) => {
-                value.leak = true;
-
-                helpers::metacall_implementer_to_traitobj(value)
-            }
-            Err(original) => original,
-        }
+        // The value is owned by the throwable, so a copy of it is given
+        parsers::raw_to_metacallobj_untyped(unsafe { metacall_value_copy(self.value) })
     }
     /// Gets the throwable value.
     pub fn get_value<T: MetacallValue>(&self) -> Result<T, Box<dyn MetacallValue>> {
@@ -196,6 +187,16 @@ impl MetacallThrowable {
         }
     }
 
+    // Gets the thrown exception. Values that are not exceptions (for example `throw "error"` in
+    // Javascript) are described in the message of a new exception.
+    pub(crate) fn to_exception(&self) -> MetacallException {
+        match self.get_value::<MetacallException>() {
+            Ok(exception) => exception,
+            Err(value) => MetacallException::new(format!("{:?}", value), "Throwable", "", 0)
+                .expect("Debug output never contains null characters"),
+        }
+    }
+
     #[doc(hidden)]
     pub fn into_raw(self) -> *mut c_void {
         // It's not implemented in any loader as the time of writing this block of code.
@@ -205,19 +206,20 @@ impl MetacallThrowable {
     }
 }
 
-impl ToString for MetacallException {
-    fn to_string(&self) -> String {
-        format!(
+impl Display for MetacallException {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "[Exception(code: `{}`)]: {}",
             self.get_code(),
             self.get_message()
         )
     }
 }
-impl ToString for MetacallThrowable {
-    fn to_string(&self) -> String {
+impl Display for MetacallThrowable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let throwable_value = self.get_value_untyped();
-        format!("[Throwable]: {:#?}", throwable_value)
+        write!(f, "[Throwable]: {:#?}", throwable_value)
     }
 }
 
@@ -1,85 +1,332 @@
-use super::{MetacallError, MetacallNull, MetacallValue};
+use super::{MetacallArgs, MetacallError, MetacallFuture, MetacallRegisterError, MetacallValue};
 use crate::{
-    bindings::{metacall_value_destroy, metacall_value_to_function, metacallfv_s},
-    parsers,
+    bindings::{
+        metacall_function, metacall_function_async, metacall_function_parameter_type,
+        metacall_function_return_type, metacall_function_size, metacall_registerv,
+        metacall_value_copy, metacall_value_create_function,
+        metacall_value_create_function_closure, metacall_value_destroy, metacall_value_id,
+        metacall_value_id_METACALL_INVALID, metacall_value_id_METACALL_SIZE,
+        metacall_value_to_function, metacallfv_await_s, metacallfv_s,
+    },
+    check_initialized, cstring_enum, inspect, parsers,
+    switch::MetacallGeneration,
 };
 use std::{
-    ffi::c_void,
+    ffi::{c_void, CStr, CString},
     fmt::{self, Debug, Formatter},
+    future::Future,
+    ptr, slice,
 };
 
-/// Represents Metacall function.
+// Boxed closure registered in the core. It receives the arguments and returns the raw value that
+// is handed back to the caller.
+type MetacallClosure = Box<dyn Fn(Vec<Box<dyn MetacallValue>>) -> *mut c_void + Send + Sync>;
+
+unsafe extern "C" fn closure_invoke(
+    size: usize,
+    args: *mut *mut c_void,
+    data: *mut c_void,
+) -> *mut c_void {
+    let closure = &*(data as *const MetacallClosure);
+    let args = if size == 0 {
+        Vec::new()
+    } else {
+        // Arguments are owned by the caller and get destroyed after the call returns
+        slice::from_raw_parts(args, size)
+            .iter()
+            .map(|arg| parsers::raw_to_metacallobj_untyped(metacall_value_copy(*arg)))
+            .collect()
+    };
+
+    closure(args)
+}
+
+/// Represents Metacall function. You can get this type when returned by a function or get a
+/// function by its name with [from_name](#method.from_name). Functions can't be used anymore
+/// once Metacall is destroyed, calling them returns a `NotInitialized` error.
 pub struct MetacallFunction {
+    generation: MetacallGeneration,
     leak: bool,
+    name: Option<String>,
     value: *mut c_void,
 }
-unsafe impl Send for MetacallFunction {}
-unsafe impl Sync for MetacallFunction {}
 impl Clone for MetacallFunction {
     fn clone(&self) -> Self {
         Self {
+            generation: self.generation,
             leak: true,
+            name: self.name.clone(),
             value: self.value,
         }
     }
 }
 impl Debug for MetacallFunction {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "MetacallFunction {{ ... }}")
+        f.debug_struct("MetacallFunction")
+            .field("name", &self.name)
+            .finish()
     }
 }
 
 impl MetacallFunction {
     #[doc(hidden)]
     pub fn new_raw(value: *mut c_void) -> Self {
-        Self { leak: false, value }
+        Self {
+            generation: MetacallGeneration::current(),
+            leak: false,
+            name: None,
+            value,
+        }
     }
 
     #[doc(hidden)]
     pub fn new_raw_leak(value: *mut c_void) -> Self {
-        Self { leak: true, value }
+        Self {
+            generation: MetacallGeneration::current(),
+            leak: true,
+            name: None,
+            value,
+        }
+    }
+
+    /// Gets a function of the global scope by its name. The function is looked up once, so it
+    /// can be called repeatedly without searching it again. Usage example: ...
+    /// ```
+    /// let sum = metacall::MetacallFunction::from_name("sum").unwrap();
+    ///
+    /// for i in 0..1000 {
+    ///     sum.call::<i64>([i, i]).unwrap();
+    /// }
+    /// ```
+    pub fn from_name(name: impl ToString) -> Result<Self, MetacallError> {
+        check_initialized!(MetacallError);
+        let c_name = cstring_enum!(name, MetacallError)?;
+        let func = unsafe { metacall_function(c_name.as_ptr()) };
+
+        if func.is_null() {
+            return Err(MetacallError::FunctionNotFound);
+        }
+
+        Ok(Self {
+            generation: MetacallGeneration::current(),
+            leak: false,
+            name: c_name.into_string().ok(),
+            value: unsafe { metacall_value_create_function(func) },
+        })
+    }
+    #[doc(hidden)]
+    pub fn is_alive(&self) -> bool {
+        self.generation.is_alive()
+    }
+
+    pub(crate) fn with_name(mut self, name: &CStr) -> Self {
+        self.name = name.to_str().ok().map(String::from);
+
+        self
+    }
+
+    // Registers the closure in the core, in the global scope if a name is given, and returns
+    // a function value bound to it. The closure is never freed as the core doesn't notify us
+    // when the function gets destroyed.
+    pub(crate) fn register_inner<R: MetacallValue>(
+        name: Option<&CStr>,
+        closure: impl Fn(Vec<Box<dyn MetacallValue>>) -> R + Send + Sync + 'static,
+    ) -> Result<Self, MetacallRegisterError> {
+        check_initialized!(MetacallRegisterError);
+        let mut func = ptr::null_mut();
+        // Arguments and return value are not typed, so they are passed as they are
+        if unsafe {
+            metacall_registerv(
+                name.map_or(ptr::null(), CStr::as_ptr),
+                Some(closure_invoke),
+                &mut func,
+                metacall_value_id_METACALL_INVALID,
+                0,
+                ptr::null_mut(),
+            )
+        } != 0
+            || func.is_null()
+        {
+            return Err(MetacallRegisterError::RegisterFailure);
+        }
+
+        let closure: MetacallClosure = Box::new(move |args| closure(args).into_metacall_raw());
+        let value = unsafe {
+            metacall_value_create_function_closure(func, Box::into_raw(Box::new(closure)).cast())
+        };
+
+        Ok(Self::new_raw(value))
+    }
+    /// Creates a function from a Rust closure, so it can be passed as an argument and called
+    /// back from the scripts. Arguments are received as they are passed by the caller and the
+    /// returned value is handed back to it. Keep in mind that the closure is never freed.
+    /// Usage example: ...
+    /// ```
+    /// use metacall::{metacall, MetacallFunction, MetacallNull};
+    ///
+    /// let on_event = MetacallFunction::from_closure(|args| {
+    ///     println!("Event received: {:#?}", args);
+    ///
+    ///     MetacallNull()
+    /// })
+    /// .unwrap();
+    ///
+    /// metacall::<MetacallNull>("add_event_listener", [on_event]).unwrap();
+    /// ```
+    pub fn from_closure<R: MetacallValue>(
+        closure: impl Fn(Vec<Box<dyn MetacallValue>>) -> R + Send + Sync + 'static,
+    ) -> Result<Self, MetacallRegisterError> {
+        Self::register_inner(None, closure)
     }
 
     fn value_to_function(&self) -> *mut c_void {
         unsafe { metacall_value_to_function(self.value) }
     }
 
-    fn call_inner<T: MetacallValue>(&self, args: impl IntoIterator<Item = T>) -> *mut c_void {
-        let mut c_args = parsers::metacallobj_to_raw_args(args);
+    /// Gets the name of the function. Functions received as values are looked up in the global
+    /// scope, so it's `None` for anonymous functions, such as closures or lambdas, and for
+    /// functions only visible from a [handle](crate::MetacallHandle) scope.
+    pub fn name(&self) -> Option<String> {
+        if self.name.is_some() {
+            return self.name.clone();
+        }
+        if !self.generation.is_alive() {
+            return None;
+        }
+
+        let func = self.value_to_function();
+        inspect::loaders()
+            .ok()?
+            .into_iter()
+            .flat_map(|loader| loader.handles)
+            .flat_map(|handle| handle.functions)
+            .map(|function| function.name)
+            .find(|name| {
+                CString::new(name.as_str())
+                    .is_ok_and(|c_name| unsafe { metacall_function(c_name.as_ptr()) } == func)
+            })
+    }
+    /// Gets the number of parameters of the function. It's zero once Metacall is destroyed.
+    pub fn arity(&self) -> usize {
+        if !self.generation.is_alive() {
+            return 0;
+        }
+
+        unsafe { metacall_function_size(self.value_to_function()) }
+    }
+    /// Gets the type id of each parameter, same as the one used by
+    /// [MetacallValue](MetacallValue) implementations. It's `None` for untyped parameters.
+    pub fn parameter_types(&self) -> Vec<Option<u32>> {
+        (0..self.arity())
+            .map(|parameter| {
+                let mut id: metacall_value_id = metacall_value_id_METACALL_INVALID;
+                unsafe {
+                    metacall_function_parameter_type(self.value_to_function(), parameter, &mut id)
+                };
+
+                (id < metacall_value_id_METACALL_SIZE).then_some(id)
+            })
+            .collect()
+    }
+    /// Gets the type id of the returned value, same as the one used by
+    /// [MetacallValue](MetacallValue) implementations. It's `None` if the return type is untyped.
+    pub fn return_type(&self) -> Option<u32> {
+        if !self.generation.is_alive() {
+            return None;
+        }
+
+        let mut id: metacall_value_id = metacall_value_id_METACALL_INVALID;
+        unsafe { metacall_function_return_type(self.value_to_function(), &mut id) };
+
+        (id < metacall_value_id_METACALL_SIZE).then_some(id)
+    }
+    /// Checks if the function is asynchronous.
+    pub fn is_async(&self) -> bool {
+        self.generation.is_alive()
+            && unsafe { metacall_function_async(self.value_to_function()) == 1 }
+    }
+
+    fn call_inner(&self, args: impl MetacallArgs) -> Result<*mut c_void, MetacallError> {
+        check_initialized!(self.generation, MetacallError);
+        let mut c_args =
+            parsers::metacallobj_to_raw_args(args).map_err(MetacallError::FailedConversion)?;
         let ret: *mut c_void =
-            unsafe { metacallfv_s(self.value_to_function(), c_args.as_mut_ptr(), 0) };
+            unsafe { metacallfv_s(self.value_to_function(), c_args.as_mut_ptr(), c_args.len()) };
 
         for c_arg in c_args {
             unsafe { metacall_value_destroy(c_arg) };
         }
 
-        ret
+        Ok(ret)
     }
     /// Calls the function with arguments and witout type casting([MetacallValue](MetacallValue)).
-    pub fn call_untyped<T: MetacallValue>(
+    pub fn call_untyped(
         &self,
-        args: impl IntoIterator<Item = T>,
-    ) -> Box<dyn MetacallValue> {
-        parsers::raw_to_metacallobj_untyped(self.call_inner(args))
+        args: impl MetacallArgs,
+    ) -> Result<Box<dyn MetacallValue>, MetacallError> {
+        Ok(parsers::raw_to_metacallobj_untyped(self.call_inner(args)?))
     }
     /// Calls the function without passing arguments and witout type
     /// casting([MetacallValue](MetacallValue)).
-    pub fn call_untyped_no_arg<T: MetacallValue>(&self) -> Box<dyn MetacallValue> {
-        parsers::raw_to_metacallobj_untyped(self.call_inner([] as [MetacallNull; 0]))
+    pub fn call_untyped_no_arg(&self) -> Result<Box<dyn MetacallValue>, MetacallError> {
+        self.call_untyped(())
     }
     /// Calls the function with arguments.
-    pub fn call<T: MetacallValue, U: MetacallValue>(
-        &self,
-        args: impl IntoIterator<Item = U>,
-    ) -> Result<T, MetacallError> {
-        match parsers::raw_to_metacallobj::<T>(self.call_inner(args)) {
+    pub fn call<T: MetacallValue>(&self, args: impl MetacallArgs) -> Result<T, MetacallError> {
+        match parsers::raw_to_metacallobj::<T>(self.call_inner(args)?) {
             Ok(ret) => Ok(ret),
-            Err(original) => Err(MetacallError::FailedCasting(original)),
+            Err(original) => Err(MetacallError::from_failed_casting(original)),
         }
     }
     /// Calls the function without arguments.
     pub fn call_no_arg<T: MetacallValue>(&self) -> Result<T, MetacallError> {
-        self.call::<T, MetacallNull>([])
+        self.call::<T>(())
+    }
+
+    fn await_call_inner(&self, args: impl MetacallArgs) -> Result<MetacallFuture, MetacallError> {
+        check_initialized!(self.generation, MetacallError);
+        let func = self.value_to_function();
+        let mut c_args =
+            parsers::metacallobj_to_raw_args(args).map_err(MetacallError::FailedConversion)?;
+        let future = MetacallFuture::from_await(|resolve, reject, data| unsafe {
+            metacallfv_await_s(
+                func,
+                c_args.as_mut_ptr(),
+                c_args.len(),
+                resolve,
+                reject,
+                data,
+            )
+        });
+
+        for c_arg in c_args {
+            unsafe { metacall_value_destroy(c_arg) };
+        }
+
+        future.ok_or(MetacallError::AwaitFailure)
+    }
+    /// Calls an asynchronous function with arguments and awaits it. The function is called right
+    /// away and the returned future resolves when the function does. A rejection is given in the
+    /// `Thrown` error variant if it's an exception, or in the `Rejected` one otherwise.
+    /// Usage example: ...
+    /// ```
+    /// let fetch = metacall::MetacallFunction::from_name("fetch_user").unwrap();
+    ///
+    /// async fn handler(fetch: metacall::MetacallFunction) {
+    ///     let user = fetch.await_call::<String>([1]).await.unwrap();
+    /// }
+    /// ```
+    pub fn await_call<T: MetacallValue>(
+        &self,
+        args: impl MetacallArgs,
+    ) -> impl Future<Output = Result<T, MetacallError>> {
+        MetacallFuture::await_typed(self.await_call_inner(args))
+    }
+    /// Calls an asynchronous function without arguments and awaits it.
+    pub fn await_call_no_arg<T: MetacallValue>(
+        &self,
+    ) -> impl Future<Output = Result<T, MetacallError>> {
+        self.await_call::<T>(())
     }
 
     #[doc(hidden)]
@@ -92,7 +339,8 @@ impl MetacallFunction {
 
 impl Drop for MetacallFunction {
     fn drop(&mut self) {
-        if !self.leak {
+        // The value was freed along with the runtime
+        if !self.leak && self.generation.is_alive() {
             unsafe { metacall_value_destroy(self.value) }
         }
     }
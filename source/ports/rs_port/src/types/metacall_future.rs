@@ -1,130 +1,222 @@
-use super::{MetacallNull, MetacallValue};
+use super::{MetacallError, MetacallNull, MetacallValue};
 use crate::{
-    bindings::{metacall_await_future, metacall_value_destroy, metacall_value_to_future},
-    helpers, parsers,
+    bindings::{
+        metacall_await_future, metacall_value_copy, metacall_value_destroy, metacall_value_id,
+        metacall_value_to_future,
+    },
+    parsers,
+    switch::MetacallGeneration,
 };
 use std::{
     ffi::c_void,
     fmt::{self, Debug, Formatter},
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
     ptr,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
 };
 
-/// Function pointer type used for resolving/rejecting Metacall futures. The first argument is the result
-/// and the second argument is the data that you may want to access when the function gets called.
-/// Checkout [MetacallFuture resolve](MetacallFuture#method.then) or
-/// [MetacallFuture reject](MetacallFuture#method.catch) for usage.
-pub type MetacallFutureHandler = fn(Box<dyn MetacallValue>, Box<dyn MetacallValue>);
+// Boxed closure used for resolving/rejecting Metacall futures. It receives the result and returns
+// the raw value that is handed back to the core.
+type MetacallFutureCallback = Box<dyn FnOnce(Box<dyn MetacallValue>) -> *mut c_void + Send>;
+
+// Callback called by the core when an awaited function or future gets resolved/rejected.
+pub(crate) type MetacallAwaitCallback =
+    Option<unsafe extern "C" fn(*mut c_void, *mut c_void) -> *mut c_void>;
+
+/// Output of a [MetacallFuture](MetacallFuture) when it's awaited natively. `Ok` holds the resolved
+/// value and `Err` holds the rejected value.
+pub type MetacallFutureResult = Result<Box<dyn MetacallValue>, Box<dyn MetacallValue>>;
+
+#[derive(Default)]
+struct MetacallFutureHandlers {
+    reject: Option<MetacallFutureCallback>,
+    resolve: Option<MetacallFutureCallback>,
+}
+
+#[derive(Default)]
+struct MetacallFutureShared {
+    handlers: MetacallFutureHandlers,
+    result: Option<MetacallFutureResult>,
+    waker: Option<Waker>,
+}
+
+// Shared between the Rust future and the resolve/reject callbacks called by the core.
+#[derive(Default)]
+struct MetacallFutureState(Mutex<MetacallFutureShared>);
+unsafe impl Send for MetacallFutureState {}
+unsafe impl Sync for MetacallFutureState {}
+impl MetacallFutureState {
+    unsafe fn complete(upper_data: *mut c_void, result: MetacallFutureResult) {
+        let state = Arc::from_raw(upper_data as *const MetacallFutureState);
+        // The handlers are taken out so they don't run while the lock is held
+        let handlers = std::mem::take(&mut state.0.lock().unwrap().handlers);
+
+        // A handler can turn both a resolved and a rejected value into a new resolved value. A
+        // panic can't unwind into the core, so the future gets rejected instead
+        let result = panic::catch_unwind(AssertUnwindSafe(|| match result {
+            Ok(value) => match handlers.resolve {
+                Some(resolve) => Ok(parsers::raw_to_metacallobj_untyped(resolve(value))),
+                None => Ok(value),
+            },
+            Err(value) => match handlers.reject {
+                Some(reject) => Ok(parsers::raw_to_metacallobj_untyped(reject(value))),
+                None => Err(value),
+            },
+        }))
+        .unwrap_or_else(|_| Err(Box::new(MetacallNull())));
+
+        let waker = {
+            let mut shared = state.0.lock().unwrap();
+            shared.result = Some(result);
+
+            shared.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
 
 /// Represents MetacallFuture. Keep in mind that it's not supported to pass a future as an argument.
 /// Usage example: ...
 /// ```
-/// use metacall::{MetacallValue, MetacallFuture, metacall};
-///
-/// fn resolve(result: impl MetacallValue, data: impl MetacallValue) {
-///     println!("Resolve:: result: {:#?}, data: {:#?}", result, data);
-/// }
+/// use metacall::{MetacallNull, MetacallFuture, metacall};
 ///
-/// fn reject(result: impl MetacallValue, data: impl MetacallValue) {
-///     println!("Reject:: result: {:#?}, data: {:#?}", result, data);
-/// }
+/// let prefix = String::from("Resolve::");
 ///
 /// let future = metacall::<MetacallFuture>("async_function", [1]).unwrap();
-/// future.then(resolve).catch(reject).await_fut();
+/// future
+///     .then(move |result| {
+///         println!("{} {:#?}", prefix, result);
+///
+///         // This value is handed back to the core
+///         MetacallNull()
+///     })
+///     .catch(|result| {
+///         println!("Reject:: {:#?}", result);
+///
+///         MetacallNull()
+///     })
+///     .await_fut();
+/// ```
+/// It also implements [Future](std::future::Future), so it can be awaited inside any async
+/// runtime. Callbacks added with [then](#method.then) or [catch](#method.catch) are applied before
+/// the output is produced, and a handled rejection becomes `Ok`. It gets rejected with
+/// [MetacallNull](MetacallNull) if Metacall is destroyed before it settles. For example: ...
+/// ```
+/// use metacall::{MetacallFuture, metacall};
+///
+/// async fn call_async_function() {
+///     let future = metacall::<MetacallFuture>("async_function", [1]).unwrap();
+///
+///     match future.await {
+///         Ok(resolved) => println!("Resolve:: {:#?}", resolved),
+///         Err(rejected) => println!("Reject:: {:#?}", rejected),
+///     }
+/// }
 /// ```
 #[repr(C)]
 pub struct MetacallFuture {
-    data: *mut dyn MetacallValue,
+    generation: MetacallGeneration,
+    handlers: MetacallFutureHandlers,
     leak: bool,
-    reject: Option<MetacallFutureHandler>,
-    resolve: Option<MetacallFutureHandler>,
+    state: Option<Arc<MetacallFutureState>>,
     value: *mut c_void,
 }
-unsafe impl Send for MetacallFuture {}
-unsafe impl Sync for MetacallFuture {}
 impl Clone for MetacallFuture {
     fn clone(&self) -> Self {
+        // Handlers are consumed when the future settles, so they are not shared with clones
         Self {
-            data: self.data,
+            generation: self.generation,
+            handlers: MetacallFutureHandlers::default(),
             leak: true,
-            reject: self.reject,
-            resolve: self.resolve,
+            state: self.state.clone(),
             value: self.value,
         }
     }
 }
 impl Debug for MetacallFuture {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let boxed_data = unsafe { Box::from_raw(self.data) };
-        let data = if boxed_data.is::<MetacallNull>() {
-            None
-        } else {
-            Some(format!("{:#?}", boxed_data))
-        };
-        Box::leak(boxed_data);
-
-        let resolve = if self.resolve.is_none() {
+        let resolve = if self.handlers.resolve.is_none() {
             "None"
         } else {
             "Some"
         };
-        let reject = if self.reject.is_none() {
+        let reject = if self.handlers.reject.is_none() {
             "None"
         } else {
             "Some"
         };
 
         f.debug_struct("MetacallFuture")
-            .field("data", &data)
             .field("resolve", &resolve)
             .field("reject", &reject)
             .finish()
     }
 }
 
-type MetacallFutureFFIData = (
-    // Resolve
-    Option<MetacallFutureHandler>,
-    // Reject
-    Option<MetacallFutureHandler>,
-    // User data
-    *mut dyn MetacallValue,
-);
-
 unsafe extern "C" fn resolver(resolve_data: *mut c_void, upper_data: *mut c_void) -> *mut c_void {
-    let (resolve, _, data) = *Box::from_raw(upper_data as *mut MetacallFutureFFIData);
-    let user_data = Box::from_raw(data);
+    let handlers = Box::from_raw(upper_data as *mut MetacallFutureHandlers);
+    // The value is owned by the core and gets destroyed after the callback returns
+    let value = metacall_value_copy(resolve_data);
 
-    (resolve.unwrap())(
-        parsers::raw_to_metacallobj_untyped_leak(resolve_data),
-        user_data,
-    );
-
-    ptr::null_mut()
+    match handlers.resolve {
+        // A panic can't unwind into the core, so nothing is handed back instead
+        Some(resolve) => panic::catch_unwind(AssertUnwindSafe(|| {
+            resolve(parsers::raw_to_metacallobj_untyped(value))
+        }))
+        .unwrap_or(ptr::null_mut()),
+        // Without a handler the value is passed through
+        None => value,
+    }
 }
 unsafe extern "C" fn rejecter(reject_data: *mut c_void, upper_data: *mut c_void) -> *mut c_void {
-    let (_, reject, data) = *Box::from_raw(upper_data as *mut MetacallFutureFFIData);
-    let user_data = Box::from_raw(data);
+    let handlers = Box::from_raw(upper_data as *mut MetacallFutureHandlers);
+    // The value is owned by the core and gets destroyed after the callback returns
+    let value = metacall_value_copy(reject_data);
 
-    (reject.unwrap())(
-        parsers::raw_to_metacallobj_untyped_leak(reject_data),
-        user_data,
-    );
+    match handlers.reject {
+        // A panic can't unwind into the core, so nothing is handed back instead
+        Some(reject) => panic::catch_unwind(AssertUnwindSafe(|| {
+            reject(parsers::raw_to_metacallobj_untyped(value))
+        }))
+        .unwrap_or(ptr::null_mut()),
+        // Without a handler the value is passed through
+        None => value,
+    }
+}
+unsafe extern "C" fn poll_resolver(
+    resolve_data: *mut c_void,
+    upper_data: *mut c_void,
+) -> *mut c_void {
+    // The value is owned by the core and gets destroyed after the callback returns
+    let result = parsers::raw_to_metacallobj_untyped(metacall_value_copy(resolve_data));
+    MetacallFutureState::complete(upper_data, Ok(result));
 
     ptr::null_mut()
 }
+unsafe extern "C" fn poll_rejecter(
+    reject_data: *mut c_void,
+    upper_data: *mut c_void,
+) -> *mut c_void {
+    // The value is owned by the core and gets destroyed after the callback returns
+    let result = parsers::raw_to_metacallobj_untyped(metacall_value_copy(reject_data));
+    MetacallFutureState::complete(upper_data, Err(result));
 
-impl MetacallFuture {
-    fn create_null_data() -> *mut dyn MetacallValue {
-        Box::into_raw(helpers::metacall_implementer_to_traitobj(MetacallNull()))
-    }
+    ptr::null_mut()
+}
 
+impl MetacallFuture {
     #[doc(hidden)]
     pub fn new_raw(value: *mut c_void) -> Self {
         Self {
-            data: Self::create_null_data(),
+            generation: MetacallGeneration::current(),
+            handlers: MetacallFutureHandlers::default(),
             leak: false,
-            reject: None,
-            resolve: None,
+            state: None,
             value,
         }
     }
@@ -132,76 +224,126 @@ impl MetacallFuture {
     #[doc(hidden)]
     pub fn new_raw_leak(value: *mut c_void) -> Self {
         Self {
-            data: Self::create_null_data(),
+            generation: MetacallGeneration::current(),
+            handlers: MetacallFutureHandlers::default(),
             leak: true,
-            reject: None,
-            resolve: None,
+            state: None,
             value,
         }
     }
 
-    /// Adds a resolve callback.
-    pub fn then(mut self, resolve: MetacallFutureHandler) -> Self {
-        self.resolve = Some(resolve);
+    // Awaits through the core with the given call, which receives the callbacks and their data.
+    // The result is delivered to the returned future when it's awaited natively. Returns `None`
+    // if the core failed to await.
+    pub(crate) fn from_await(
+        call: impl FnOnce(MetacallAwaitCallback, MetacallAwaitCallback, *mut c_void) -> *mut c_void,
+    ) -> Option<Self> {
+        let state = Arc::new(MetacallFutureState::default());
+        // The callbacks take back the ownership of this reference
+        let upper_data = Arc::into_raw(state.clone()) as *mut c_void;
+        let value = call(Some(poll_resolver), Some(poll_rejecter), upper_data);
 
-        self
+        if value.is_null() {
+            // Nothing is awaited, so the callbacks are never called
+            unsafe { drop(Arc::from_raw(upper_data as *const MetacallFutureState)) };
+
+            return None;
+        }
+
+        Some(Self {
+            generation: MetacallGeneration::current(),
+            handlers: MetacallFutureHandlers::default(),
+            leak: false,
+            state: Some(state),
+            value,
+        })
+    }
+    // Awaits the future natively and casts the resolved value.
+    pub(crate) async fn await_typed<T: MetacallValue>(
+        future: Result<Self, MetacallError>,
+    ) -> Result<T, MetacallError> {
+        match future?.await {
+            Ok(resolved) => parsers::metacallobj_untyped_to_typed::<T>(resolved)
+                .map_err(MetacallError::from_failed_casting),
+            Err(rejected) => Err(MetacallError::from_rejected(rejected)),
+        }
+    }
+    // Awaits the value returned by a call natively if it's a future and casts the resolved value,
+    // otherwise the returned value is casted right away.
+    pub(crate) fn await_returned<T: MetacallValue>(
+        ret: Result<*mut c_void, MetacallError>,
+    ) -> impl Future<Output = Result<T, MetacallError>> {
+        // The returned value is taken now, so it's not leaked if the future is never polled
+        let ret = ret.map(|ret| {
+            if !ret.is_null() && unsafe { metacall_value_id(ret) } == 12 {
+                Ok(Self::new_raw(ret))
+            } else {
+                Err(parsers::raw_to_metacallobj::<T>(ret)
+                    .map_err(MetacallError::from_failed_casting))
+            }
+        });
+
+        async move {
+            match ret? {
+                Ok(future) => Self::await_typed(Ok(future)).await,
+                // Synchronous calls are already done
+                Err(ready) => ready,
+            }
+        }
     }
 
-    /// Adds a reject callback.
-    pub fn catch(mut self, reject: MetacallFutureHandler) -> Self {
-        self.reject = Some(reject);
+    /// Adds a resolve callback. The value returned by the callback is handed back to the core,
+    /// so it becomes the resolved value of the future returned by [await_fut](#method.await_fut).
+    pub fn then<R: MetacallValue>(
+        mut self,
+        resolve: impl FnOnce(Box<dyn MetacallValue>) -> R + Send + 'static,
+    ) -> Self {
+        self.handlers.resolve = Some(Box::new(move |result| resolve(result).into_metacall_raw()));
 
         self
     }
 
-    /// Adds data.
-    pub fn data(mut self, data: impl MetacallValue) -> Self {
-        unsafe { drop(Box::from_raw(self.data)) };
-
-        self.data = Box::into_raw(Box::new(data) as Box<dyn MetacallValue>);
+    /// Adds a reject callback. The value returned by the callback is handed back to the core,
+    /// so it becomes the resolved value of the future returned by [await_fut](#method.await_fut).
+    pub fn catch<R: MetacallValue>(
+        mut self,
+        reject: impl FnOnce(Box<dyn MetacallValue>) -> R + Send + 'static,
+    ) -> Self {
+        self.handlers.reject = Some(Box::new(move |result| reject(result).into_metacall_raw()));
 
         self
     }
 
-    /// Awaits the future.
-    pub fn await_fut(self) {
-        let resolve_is_some = self.resolve.is_some();
-        let reject_is_some = self.reject.is_some();
+    /// Awaits the future and returns the value created by the core from the callbacks, which
+    /// is usually another [MetacallFuture](MetacallFuture) that can be chained. It returns
+    /// [MetacallNull](MetacallNull) once Metacall is destroyed.
+    pub fn await_fut(mut self) -> Box<dyn MetacallValue> {
+        if !self.generation.is_alive() {
+            return Box::new(MetacallNull());
+        }
 
-        unsafe {
-            metacall_value_destroy(metacall_await_future(
+        let handlers = std::mem::take(&mut self.handlers);
+        let (resolve, reject, upper_data): (MetacallAwaitCallback, MetacallAwaitCallback, _) =
+            if handlers.resolve.is_none() && handlers.reject.is_none() {
+                (None, None, ptr::null_mut())
+            } else {
+                // Whichever callback gets called takes back the ownership of the handlers, so
+                // both are given to the core even if only one of the handlers is set
+                (
+                    Some(resolver),
+                    Some(rejecter),
+                    Box::into_raw(Box::new(handlers)) as *mut c_void,
+                )
+            };
+
+        parsers::raw_to_metacallobj_untyped(unsafe {
+            metacall_await_future(
                 metacall_value_to_future(self.value),
-                if resolve_is_some {
-                    Some(resolver)
-                } else {
-                    None
-                },
-                if reject_is_some { Some(rejecter) } else { None },
-                // TODO: Solve the memory leak that happens here
-                // For reproducing the error, use the following commands:
-                // cargo test --no-run
-                // valgrind --trace-children=yes --leak-check=full --tool=memcheck --suppressions=../../../source/tests/memcheck/valgrind-node.supp ./target/debug/deps/metacall_test-248af33824f71bd1 &> output
-                // ==20664== 60 (32 direct, 28 indirect) bytes in 1 blocks are definitely lost in loss record 11 of 35
-                // ==20664==    at 0x4842839: malloc (in /usr/libexec/valgrind/vgpreload_memcheck-amd64-linux.so)
-                // ==20664==    by 0x17B549: alloc (alloc.rs:93)
-                // ==20664==    by 0x17B549: alloc::alloc::Global::alloc_impl (alloc.rs:175)
-                // ==20664==    by 0x17B342: allocate (alloc.rs:235)
-                // ==20664==    by 0x17B342: alloc::alloc::exchange_malloc (alloc.rs:324)
-                // ==20664==    by 0x1873D0: new<(core::option::Option<fn(alloc::boxed::Box<dyn metacall::types::metacall_value::MetacallValue, alloc::alloc::Global>, alloc::boxed::Box<dyn metacall::types::metacall_value::MetacallValue, alloc::alloc::Global>)>, core::option::Option<fn(alloc::boxed::Box<dyn metacall::types::metacall_value::MetacallValue, alloc::alloc::Global>, alloc::boxed::Box<dyn metacall::types::metacall_value::MetacallValue, alloc::alloc::Global>)>, *mut dyn metacall::types::metacall_value::MetacallValue)> (boxed.rs:217)
-                // ==20664==    by 0x1873D0: metacall::types::metacall_future::MetacallFuture::await_fut (metacall_future.rs:182)
-                // ==20664==    by 0x1296E6: metacall_test::test_future::{{closure}} (metacall_test.rs:202)
-                // ==20664==    by 0x1286A2: metacall_test::generate_test_custom_validation (metacall_test.rs:42)
-                // ==20664==    by 0x12625A: metacall_test::test_future (metacall_test.rs:193)
-                // ==20664==    by 0x126954: metacall_test::metacall (metacall_test.rs:368)
-                // ==20664==    by 0x129736: metacall_test::metacall::{{closure}} (metacall_test.rs:337)
-                // ==20664==    by 0x1256B4: core::ops::function::FnOnce::call_once (function.rs:250)
-                // ==20664==    by 0x166EBE: call_once<fn() -> core::result::Result<(), alloc::string::String>, ()> (function.rs:250)
-                // ==20664==    by 0x166EBE: test::__rust_begin_short_backtrace (lib.rs:655)
-                // ==20664==    by 0x13456B: {closure#1} (lib.rs:646)
-                // ==20664==    by 0x13456B: core::ops::function::FnOnce::call_once{{vtable-shim}} (function.rs:250)
-                Box::into_raw(Box::new((self.resolve, self.reject, self.data))) as *mut c_void,
-            ))
-        };
+                resolve,
+                reject,
+                upper_data,
+            )
+        })
     }
 
     #[doc(hidden)]
@@ -214,9 +356,64 @@ impl MetacallFuture {
     }
 }
 
+impl Future for MetacallFuture {
+    type Output = MetacallFutureResult;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        // Nothing is going to settle the future once Metacall is destroyed
+        if !this.generation.is_alive() {
+            let result = this
+                .state
+                .as_ref()
+                .and_then(|state| state.0.lock().unwrap().result.take());
+
+            return Poll::Ready(result.unwrap_or_else(|| Err(Box::new(MetacallNull()))));
+        }
+
+        let state = match &this.state {
+            Some(state) => state.clone(),
+            None => {
+                let state = Arc::new(MetacallFutureState::default());
+                {
+                    let mut shared = state.0.lock().unwrap();
+                    shared.handlers = std::mem::take(&mut this.handlers);
+                    shared.waker = Some(cx.waker().clone());
+                }
+                this.state = Some(state.clone());
+
+                // The callbacks take back the ownership of this reference
+                let upper_data = Arc::into_raw(state.clone()) as *mut c_void;
+                unsafe {
+                    metacall_value_destroy(metacall_await_future(
+                        metacall_value_to_future(this.value),
+                        Some(poll_resolver),
+                        Some(poll_rejecter),
+                        upper_data,
+                    ))
+                };
+
+                state
+            }
+        };
+
+        let mut shared = state.0.lock().unwrap();
+        match shared.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+
+                Poll::Pending
+            }
+        }
+    }
+}
+
 impl Drop for MetacallFuture {
     fn drop(&mut self) {
-        if !self.leak {
+        // The value was freed along with the runtime
+        if !self.leak && self.generation.is_alive() {
             unsafe { metacall_value_destroy(self.value) };
         }
     }
@@ -0,0 +1,203 @@
+use super::{MetacallArgs, MetacallError, MetacallFunction, MetacallValue};
+use crate::{
+    bindings::{
+        metacall_clear, metacall_handle_export, metacall_handle_function, metacall_handle_id,
+        metacall_value_count, metacall_value_create_function, metacall_value_destroy,
+        metacall_value_to_array, metacall_value_to_map, metacallhv_s,
+    },
+    check_initialized, cstring_enum, parsers,
+    switch::MetacallGeneration,
+};
+use std::{
+    ffi::{c_void, CStr},
+    fmt::{self, Debug, Formatter},
+    slice,
+};
+
+// Used for documentation.
+#[allow(unused_imports)]
+use crate::loaders;
+
+/// Represents a Metacall handle, the private scope of the scripts loaded with
+/// [from_file_handle](loaders::from_file_handle) or [from_memory_handle](loaders::from_memory_handle).
+/// Functions of a handle are not visible from the global scope, so scripts exporting the same
+/// names can be loaded side by side. The scripts get unloaded when the handle is dropped, or
+/// along with the rest when Metacall is destroyed. Usage example: ...
+/// ```
+/// let script = "function greet() { return 'hi there!' }; module.exports = { greet };";
+/// let handle = metacall::loaders::from_memory_handle("node", script).unwrap();
+///
+/// assert_eq!(handle.exports(), vec![String::from("greet")]);
+///
+/// let greet = handle.call_no_arg::<String>("greet").unwrap();
+/// ```
+pub struct MetacallHandle {
+    generation: MetacallGeneration,
+    value: *mut c_void,
+}
+impl Debug for MetacallHandle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetacallHandle")
+            .field("id", &self.get_id())
+            .finish()
+    }
+}
+
+impl MetacallHandle {
+    #[doc(hidden)]
+    pub fn new_raw(value: *mut c_void) -> Self {
+        Self {
+            generation: MetacallGeneration::current(),
+            value,
+        }
+    }
+
+    /// Gets the handle id, which is usually the name of the loaded script.
+    pub fn get_id(&self) -> String {
+        if !self.generation.is_alive() {
+            return String::new();
+        }
+
+        let id = unsafe { metacall_handle_id(self.value) };
+
+        if id.is_null() {
+            return String::new();
+        }
+
+        String::from(unsafe { CStr::from_ptr(id) }.to_str().unwrap())
+    }
+
+    /// Gets the names of everything exported by the handle.
+    pub fn exports(&self) -> Vec<String> {
+        if !self.generation.is_alive() {
+            return Vec::new();
+        }
+
+        // The exports map is a new copy of the scope, so it must be destroyed after reading it
+        let exports = unsafe { metacall_handle_export(self.value) };
+
+        if exports.is_null() {
+            return Vec::new();
+        }
+
+        let names = unsafe {
+            slice::from_raw_parts(
+                metacall_value_to_map(exports),
+                metacall_value_count(exports),
+            )
+        }
+        .iter()
+        .filter_map(|pair| {
+            let pair = unsafe { slice::from_raw_parts(metacall_value_to_array(*pair), 2) };
+
+            parsers::raw_to_metacallobj_leak::<String>(pair[0]).ok()
+        })
+        .collect();
+
+        unsafe { metacall_value_destroy(exports) };
+
+        names
+    }
+
+    #[doc(hidden)]
+    pub fn is_alive(&self) -> bool {
+        self.generation.is_alive()
+    }
+
+    fn get_function_inner(&self, c_name: &CStr) -> Result<*mut c_void, MetacallError> {
+        check_initialized!(self.generation, MetacallError);
+        let func = unsafe { metacall_handle_function(self.value, c_name.as_ptr()) };
+
+        if func.is_null() {
+            return Err(MetacallError::FunctionNotFound);
+        }
+
+        Ok(func)
+    }
+    /// Gets a [function](MetacallFunction) of the handle by its name.
+    pub fn get_function(&self, name: impl ToString) -> Result<MetacallFunction, MetacallError> {
+        let c_name = cstring_enum!(name, MetacallError)?;
+        let func = self.get_function_inner(&c_name)?;
+
+        Ok(
+            MetacallFunction::new_raw(unsafe { metacall_value_create_function(func) })
+                .with_name(&c_name),
+        )
+    }
+
+    fn call_inner(
+        &self,
+        name: impl ToString,
+        args: impl MetacallArgs,
+    ) -> Result<*mut c_void, MetacallError> {
+        let c_name = cstring_enum!(name, MetacallError)?;
+        // Check the function first as calling a missing one doesn't tell us anything
+        self.get_function_inner(&c_name)?;
+
+        let mut c_args =
+            parsers::metacallobj_to_raw_args(args).map_err(MetacallError::FailedConversion)?;
+        let ret = unsafe {
+            metacallhv_s(
+                self.value,
+                c_name.as_ptr(),
+                c_args.as_mut_ptr(),
+                c_args.len(),
+            )
+        };
+
+        for c_arg in c_args {
+            unsafe { metacall_value_destroy(c_arg) };
+        }
+
+        Ok(ret)
+    }
+    /// Calls a function of the handle witout type casting([MetacallValue](MetacallValue)).
+    pub fn call_untyped(
+        &self,
+        name: impl ToString,
+        args: impl MetacallArgs,
+    ) -> Result<Box<dyn MetacallValue>, MetacallError> {
+        Ok(parsers::raw_to_metacallobj_untyped(
+            self.call_inner(name, args)?,
+        ))
+    }
+    /// Calls a function of the handle witout type casting([MetacallValue](MetacallValue)) and
+    /// without passing arguments.
+    pub fn call_untyped_no_arg(
+        &self,
+        name: impl ToString,
+    ) -> Result<Box<dyn MetacallValue>, MetacallError> {
+        self.call_untyped(name, ())
+    }
+    /// Calls a function of the handle.
+    pub fn call<T: MetacallValue>(
+        &self,
+        name: impl ToString,
+        args: impl MetacallArgs,
+    ) -> Result<T, MetacallError> {
+        match parsers::raw_to_metacallobj::<T>(self.call_inner(name, args)?) {
+            Ok(ret) => Ok(ret),
+            Err(original) => Err(MetacallError::from_failed_casting(original)),
+        }
+    }
+    /// Calls a function of the handle without passing arguments.
+    pub fn call_no_arg<T: MetacallValue>(&self, name: impl ToString) -> Result<T, MetacallError> {
+        self.call::<T>(name, ())
+    }
+}
+
+impl Drop for MetacallHandle {
+    fn drop(&mut self) {
+        // The scripts were unloaded along with the runtime
+        if self.generation.is_alive() {
+            unsafe { metacall_clear(self.value) };
+        }
+    }
+}
+
+/// Handle cached process-wide by the functions generated with [bind](crate::bind), so the script
+/// is only loaded once no matter how many threads call them.
+#[doc(hidden)]
+pub struct MetacallBindHandle(pub MetacallHandle);
+// The handle is only used, replaced and dropped while holding the lock of its cache
+unsafe impl Send for MetacallBindHandle {}
@@ -0,0 +1,121 @@
+use std::{slice, vec};
+
+/// Represents Metacall map while keeping the order of its entries, unlike
+/// [HashMap](std::collections::HashMap) or [BTreeMap](std::collections::BTreeMap). Keys don't
+/// need to be hashable or ordered, so any [MetacallValue](crate::MetacallValue) can be used as a
+/// key. Usage example: ...
+/// ```
+/// let mut map = metacall::MetacallMap::new();
+/// map.insert(String::from("first"), 1);
+/// map.insert(String::from("second"), 2);
+///
+/// let map = metacall::metacall::<metacall::MetacallMap<String, i64>>("x", [map]).unwrap();
+/// for (key, value) in map {
+///     println!("{key}: {value}");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetacallMap<K, V> {
+    entries: Vec<(K, V)>,
+}
+impl<K, V> Default for MetacallMap<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<K, V> MetacallMap<K, V> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// Checks if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the entries in order.
+    pub fn iter(&self) -> slice::Iter<'_, (K, V)> {
+        self.entries.iter()
+    }
+
+    /// Consumes the map and returns its entries in order.
+    pub fn into_entries(self) -> Vec<(K, V)> {
+        self.entries
+    }
+}
+impl<K: PartialEq, V> MetacallMap<K, V> {
+    /// Inserts an entry at the end of the map. If the key is already there, its value gets
+    /// replaced in place and the old one is returned.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.get_mut(&key) {
+            Some(old) => Some(std::mem::replace(old, value)),
+            None => {
+                self.entries.push((key, value));
+
+                None
+            }
+        }
+    }
+
+    /// Gets the value of a key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.entries
+            .iter()
+            .find(|(entry_key, _)| entry_key == key)
+            .map(|(_, value)| value)
+    }
+    /// Gets a mutable reference to the value of a key.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.entries
+            .iter_mut()
+            .find(|(entry_key, _)| entry_key == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Removes a key and returns its value, keeping the order of the other entries.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self
+            .entries
+            .iter()
+            .position(|(entry_key, _)| entry_key == key)?;
+
+        Some(self.entries.remove(index).1)
+    }
+}
+
+impl<K, V> From<Vec<(K, V)>> for MetacallMap<K, V> {
+    fn from(entries: Vec<(K, V)>) -> Self {
+        Self { entries }
+    }
+}
+impl<K, V> FromIterator<(K, V)> for MetacallMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+impl<K, V> IntoIterator for MetacallMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = vec::IntoIter<(K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+impl<'a, K, V> IntoIterator for &'a MetacallMap<K, V> {
+    type Item = &'a (K, V);
+    type IntoIter = slice::Iter<'a, (K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
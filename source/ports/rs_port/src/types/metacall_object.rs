@@ -1,17 +1,19 @@
 use super::{
-    MetacallError, MetacallGetAttributeError, MetacallNull, MetacallSetAttributeError,
-    MetacallValue,
+    MetacallArgs, MetacallError, MetacallFuture, MetacallGetAttributeError,
+    MetacallSetAttributeError, MetacallValue,
 };
 use crate::{
     bindings::{
         metacall_object_get, metacall_object_set, metacall_value_destroy, metacall_value_to_object,
         metacallv_object,
     },
-    cstring_enum, parsers,
+    check_initialized, cstring_enum, parsers,
+    switch::MetacallGeneration,
 };
 use std::{
     ffi::c_void,
     fmt::{self, Debug, Formatter},
+    future::Future,
 };
 
 // Used for documentation.
@@ -19,16 +21,17 @@ use std::{
 use super::MetacallClass;
 
 /// Represents Metacall Object. You can get this type when returned by a function or create one from
-/// a class with [create_object](MetacallClass#method.create_object).
+/// a class with [create_object](MetacallClass#method.create_object). Objects can't be used anymore
+/// once Metacall is destroyed, using them returns a `NotInitialized` error.
 pub struct MetacallObject {
+    generation: MetacallGeneration,
     value: *mut c_void,
     leak: bool,
 }
-unsafe impl Send for MetacallObject {}
-unsafe impl Sync for MetacallObject {}
 impl Clone for MetacallObject {
     fn clone(&self) -> Self {
         Self {
+            generation: self.generation,
             leak: true,
             value: self.value,
         }
@@ -43,18 +46,27 @@ impl Debug for MetacallObject {
 impl MetacallObject {
     #[doc(hidden)]
     pub fn new_raw(value: *mut c_void) -> Self {
-        Self { value, leak: false }
+        Self {
+            generation: MetacallGeneration::current(),
+            value,
+            leak: false,
+        }
     }
 
     #[doc(hidden)]
     pub fn new_raw_leak(value: *mut c_void) -> Self {
-        Self { value, leak: true }
+        Self {
+            generation: MetacallGeneration::current(),
+            value,
+            leak: true,
+        }
     }
 
     fn get_attribute_inner(
         &self,
         name: impl ToString,
     ) -> Result<*mut c_void, MetacallGetAttributeError> {
+        check_initialized!(self.generation, MetacallGetAttributeError);
         let c_name = cstring_enum!(name, MetacallGetAttributeError)?;
 
         Ok(unsafe { metacall_object_get(metacall_value_to_object(self.value), c_name.as_ptr()) })
@@ -85,8 +97,10 @@ impl MetacallObject {
         key: impl ToString,
         value: impl MetacallValue,
     ) -> Result<(), MetacallSetAttributeError> {
+        check_initialized!(self.generation, MetacallSetAttributeError);
         let c_key = cstring_enum!(key, MetacallSetAttributeError)?;
-        let c_arg = parsers::metacallobj_to_raw(value);
+        let c_arg = parsers::metacallobj_to_raw(value)
+            .map_err(MetacallSetAttributeError::FailedConversion)?;
         if unsafe {
             metacall_object_set(metacall_value_to_object(self.value), c_key.as_ptr(), c_arg)
         } != 0
@@ -99,13 +113,15 @@ impl MetacallObject {
         Ok(())
     }
 
-    fn call_method_inner<T: MetacallValue>(
+    fn call_method_inner(
         &self,
         key: impl ToString,
-        args: impl IntoIterator<Item = T>,
+        args: impl MetacallArgs,
     ) -> Result<*mut c_void, MetacallError> {
+        check_initialized!(self.generation, MetacallError);
         let c_key = cstring_enum!(key, MetacallError)?;
-        let mut c_args = parsers::metacallobj_to_raw_args(args);
+        let mut c_args =
+            parsers::metacallobj_to_raw_args(args).map_err(MetacallError::FailedConversion)?;
         let ret = unsafe {
             metacallv_object(
                 metacall_value_to_object(self.value),
@@ -122,34 +138,34 @@ impl MetacallObject {
         Ok(ret)
     }
     /// Calls an object method witout type casting([MetacallValue](MetacallValue)).
-    pub fn call_method_untyped<T: MetacallValue>(
+    pub fn call_method_untyped(
         &self,
         key: impl ToString,
-        args: impl IntoIterator<Item = T>,
+        args: impl MetacallArgs,
     ) -> Result<Box<dyn MetacallValue>, MetacallError> {
         Ok(parsers::raw_to_metacallobj_untyped(
-            self.call_method_inner::<T>(key, args)?,
+            self.call_method_inner(key, args)?,
         ))
     }
     /// Calls an object method witout type casting([MetacallValue](MetacallValue)) and
     /// without passing arguments.
-    pub fn call_method_untyped_no_arg<T: MetacallValue>(
+    pub fn call_method_untyped_no_arg(
         &self,
         key: impl ToString,
     ) -> Result<Box<dyn MetacallValue>, MetacallError> {
         Ok(parsers::raw_to_metacallobj_untyped(
-            self.call_method_inner::<T>(key, [])?,
+            self.call_method_inner(key, ())?,
         ))
     }
     /// Calls an object method.
-    pub fn call_method<T: MetacallValue, U: MetacallValue>(
+    pub fn call_method<T: MetacallValue>(
         &self,
         key: impl ToString,
-        args: impl IntoIterator<Item = U>,
+        args: impl MetacallArgs,
     ) -> Result<T, MetacallError> {
-        match parsers::raw_to_metacallobj::<T>(self.call_method_inner::<U>(key, args)?) {
+        match parsers::raw_to_metacallobj::<T>(self.call_method_inner(key, args)?) {
             Ok(ret) => Ok(ret),
-            Err(original) => Err(MetacallError::FailedCasting(original)),
+            Err(original) => Err(MetacallError::from_failed_casting(original)),
         }
     }
     /// Calls an object method without passing arguments.
@@ -157,7 +173,33 @@ impl MetacallObject {
         &self,
         key: impl ToString,
     ) -> Result<T, MetacallError> {
-        self.call_method::<T, MetacallNull>(key, [])
+        self.call_method::<T>(key, ())
+    }
+    /// Calls an object method and awaits it natively if it returns a future, so asynchronous methods can
+    /// be awaited the same way as [metacall_async](crate::metacall_async). The method is called
+    /// right away. Usage example: ...
+    /// ```
+    /// async fn handler(object: metacall::MetacallObject) {
+    ///     let user = object
+    ///         .call_method_async::<String>("fetch_user", [1])
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn call_method_async<T: MetacallValue>(
+        &self,
+        key: impl ToString,
+        args: impl MetacallArgs,
+    ) -> impl Future<Output = Result<T, MetacallError>> {
+        MetacallFuture::await_returned(self.call_method_inner(key, args))
+    }
+    /// Calls an object method without passing arguments and awaits it natively if it returns a
+    /// future.
+    pub fn call_method_async_no_arg<T: MetacallValue>(
+        &self,
+        key: impl ToString,
+    ) -> impl Future<Output = Result<T, MetacallError>> {
+        self.call_method_async::<T>(key, ())
     }
 
     #[doc(hidden)]
@@ -170,7 +212,8 @@ impl MetacallObject {
 
 impl Drop for MetacallObject {
     fn drop(&mut self) {
-        if !self.leak {
+        // The value was freed along with the runtime
+        if !self.leak && self.generation.is_alive() {
             unsafe { metacall_value_destroy(self.value) }
         }
     }
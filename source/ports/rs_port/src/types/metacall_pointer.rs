@@ -16,8 +16,6 @@ pub struct MetacallPointer {
     rust_value_leak: bool,
     value: *mut c_void,
 }
-unsafe impl Send for MetacallPointer {}
-unsafe impl Sync for MetacallPointer {}
 impl Clone for MetacallPointer {
     fn clone(&self) -> Self {
         Self {
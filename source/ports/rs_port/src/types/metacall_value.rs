@@ -1,20 +1,26 @@
 use super::{
-    MetacallClass, MetacallException, MetacallFunction, MetacallFuture, MetacallNull,
-    MetacallObject, MetacallPointer, MetacallThrowable,
+    MetacallAny, MetacallBuffer, MetacallClass, MetacallConversionError, MetacallException,
+    MetacallFunction, MetacallFuture, MetacallMap, MetacallNull, MetacallObject, MetacallPointer,
+    MetacallSerialError, MetacallThrowable,
 };
 use crate::{
     bindings::*,
     cstring,
-    helpers::{MetacallClone, MetacallDowncast},
-    match_metacall_value, parsers,
+    helpers::{MetacallClone, MetacallDowncast, MetacallIntoRaw},
+    parsers, serial,
 };
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     ffi::{c_char, c_int, c_void, CStr},
     fmt::Debug,
+    hash::Hash,
     slice,
 };
 
+// Used for documentation.
+#[allow(unused_imports)]
+use crate::match_metacall_value;
+
 /// Trait of any possible object in Metacall.
 /// Checkout [match_metacall_value](match_metacall_value) macro for
 /// matching trait objects of this trait. Let' see what types we can use with an example: ...
@@ -36,13 +42,7 @@ use std::{
 /// // string
 /// metacall::metacall_untyped("x", [String::from("hi")]);
 /// // buffer
-/// metacall::metacall_untyped("x", [
-///     String::from("hi")
-///     .as_bytes()
-///     .iter()
-///     .map(|&b| b as i8)
-///     .collect::<Vec<i8>>()
-/// ]);
+/// metacall::metacall_untyped("x", [metacall::MetacallBuffer::from("hi".as_bytes())]);
 /// // array
 /// metacall::metacall_untyped("x", [vec![5, 6]]);
 /// // map
@@ -71,7 +71,7 @@ use std::{
 /// // throwable?
 /// // nope! you can't pass a throwable!
 /// ```
-pub trait MetacallValue: MetacallClone + MetacallDowncast + Debug {
+pub trait MetacallValue: MetacallClone + MetacallDowncast + MetacallIntoRaw + Debug {
     // It tries to convert the raw pointer to the value or return a trait object on failure.
     #[doc(hidden)]
     fn from_metacall_raw(v: *mut c_void) -> Result<Self, Box<dyn MetacallValue>>
@@ -94,9 +94,42 @@ pub trait MetacallValue: MetacallClone + MetacallDowncast + Debug {
     fn get_metacall_id() -> u32
     where
         Self: Sized;
+    // It checks if a value with the given enum index can be converted to this type.
+    #[doc(hidden)]
+    fn accepts_metacall_id(id: u32) -> bool
+    where
+        Self: Sized,
+    {
+        id == Self::get_metacall_id()
+    }
     // It converts the value to a raw value known by the metacall core.
     #[doc(hidden)]
     fn into_metacall_raw(self) -> *mut c_void;
+
+    /// Serializes the value as JSON, same as [to_json](serial::to_json). For example: ...
+    /// ```
+    /// use metacall::MetacallValue;
+    ///
+    /// let json = vec![1, 2, 3].to_json().unwrap();
+    /// ```
+    fn to_json(&self) -> Result<String, MetacallSerialError>
+    where
+        Self: Sized + Clone,
+    {
+        serial::to_json(self.clone())
+    }
+    /// Deserializes a value from JSON, same as [from_json](serial::from_json). For example: ...
+    /// ```
+    /// use metacall::MetacallValue;
+    ///
+    /// let numbers = Vec::<i32>::from_json("[1, 2, 3]").unwrap();
+    /// ```
+    fn from_json(json: impl AsRef<str>) -> Result<Self, MetacallSerialError>
+    where
+        Self: Sized,
+    {
+        serial::from_json::<Self>(json)
+    }
 }
 /// Equivalent to Metacall boolean type.
 impl MetacallValue for bool {
@@ -112,18 +145,47 @@ impl MetacallValue for bool {
         unsafe { metacall_value_create_bool((self as c_int).try_into().unwrap()) }
     }
 }
-/// Equivalent to Metacall char type.
+/// Equivalent to Metacall char type. Non ASCII characters don't fit into a C char, so they are
+/// passed as Metacall string type, and a string of a single character can be converted back.
 impl MetacallValue for char {
     fn get_metacall_id() -> u32 {
         1
     }
+    fn accepts_metacall_id(id: u32) -> bool {
+        id == 1 || id == 7
+    }
     fn from_metacall_raw_leak(v: *mut c_void) -> Result<Self, Box<dyn MetacallValue>> {
-        let value = unsafe { metacall_value_to_char(v) as u8 as char };
+        let value = if unsafe { metacall_value_id(v) } == 7 {
+            let c_str = unsafe { CStr::from_ptr(metacall_value_to_string(v)) };
+            let mut chars = c_str.to_str().unwrap_or_default().chars();
 
-        Ok(value)
+            match (chars.next(), chars.next()) {
+                (Some(value), None) => Some(value),
+                _ => None,
+            }
+        } else {
+            // A C char holds a single byte, which is read as Latin-1
+            Some(char::from(unsafe { metacall_value_to_char(v) } as u8))
+        };
+
+        match value {
+            Some(value) => Ok(value),
+            None => Err(parsers::raw_to_metacallobj_untyped_leak(v)),
+        }
+    }
+    fn from_metacall_raw(v: *mut c_void) -> Result<Self, Box<dyn MetacallValue>> {
+        let value = Self::from_metacall_raw_leak(v);
+
+        unsafe { metacall_value_destroy(v) };
+
+        value
     }
     fn into_metacall_raw(self) -> *mut c_void {
-        unsafe { metacall_value_create_char(self as c_char) }
+        if self.is_ascii() {
+            unsafe { metacall_value_create_char(self as c_char) }
+        } else {
+            self.to_string().into_metacall_raw()
+        }
     }
 }
 /// Equivalent to Metacall short type.
@@ -168,6 +230,73 @@ impl MetacallValue for i64 {
         unsafe { metacall_value_create_long(self) }
     }
 }
+// Reads any Metacall number, doubles are only accepted if they don't have a fractional part.
+fn integer_from_raw(v: *mut c_void) -> Option<i128> {
+    let double = match unsafe { metacall_value_id(v) } {
+        2 => return Some(unsafe { metacall_value_to_short(v) } as i128),
+        3 => return Some(unsafe { metacall_value_to_int(v) } as i128),
+        4 => return Some(unsafe { metacall_value_to_long(v) } as i128),
+        5 => (unsafe { metacall_value_to_float(v) }) as f64,
+        6 => unsafe { metacall_value_to_double(v) },
+        _ => return None,
+    };
+
+    if double.fract() == 0.0 && double.abs() < i128::MAX as f64 {
+        Some(double as i128)
+    } else {
+        None
+    }
+}
+macro_rules! checked_integer {
+    ($($type:ty => $raw:ty, $create:ident, $name:literal);* $(;)?) => {
+        $(
+            #[doc = concat!(
+                "Converted to Metacall ", $name, " type. Any Metacall number can be converted ",
+                "back as long as it fits, otherwise the casting fails. Values that don't fit ",
+                "into a Metacall ", $name, " make the call fail with `FailedConversion` error."
+            )]
+            impl MetacallValue for $type {
+                fn get_metacall_id() -> u32 {
+                    <$raw>::get_metacall_id()
+                }
+                fn accepts_metacall_id(id: u32) -> bool {
+                    (2..=6).contains(&id)
+                }
+                fn from_metacall_raw_leak(v: *mut c_void) -> Result<Self, Box<dyn MetacallValue>> {
+                    match integer_from_raw(v).and_then(|value| <$type>::try_from(value).ok()) {
+                        Some(value) => Ok(value),
+                        None => Err(parsers::raw_to_metacallobj_untyped_leak(v)),
+                    }
+                }
+                fn from_metacall_raw(v: *mut c_void) -> Result<Self, Box<dyn MetacallValue>> {
+                    let value = Self::from_metacall_raw_leak(v);
+
+                    unsafe { metacall_value_destroy(v) };
+
+                    value
+                }
+                fn into_metacall_raw(self) -> *mut c_void {
+                    match <$raw>::try_from(self) {
+                        Ok(value) => unsafe { $create(value) },
+                        Err(_) => parsers::conversion_failure(MetacallConversionError::new(
+                            format!("{} doesn't fit into a Metacall {}!", self, $name),
+                        )),
+                    }
+                }
+            }
+        )*
+    };
+}
+checked_integer!(
+    u8 => i16, metacall_value_create_short, "short";
+    u16 => i32, metacall_value_create_int, "int";
+    u32 => i64, metacall_value_create_long, "long";
+    u64 => i64, metacall_value_create_long, "long";
+    usize => i64, metacall_value_create_long, "long";
+    isize => i64, metacall_value_create_long, "long";
+    i128 => i64, metacall_value_create_long, "long";
+    u128 => i64, metacall_value_create_long, "long";
+);
 /// Equivalent to Metacall float type.
 impl MetacallValue for f32 {
     fn get_metacall_id() -> u32 {
@@ -227,8 +356,25 @@ impl MetacallValue for Vec<i8> {
             .to_vec()
         })
     }
-    fn into_metacall_raw(mut self) -> *mut c_void {
-        unsafe { metacall_value_create_buffer(self.as_mut_ptr() as *mut c_void, self.len()) }
+    fn into_metacall_raw(self) -> *mut c_void {
+        unsafe { metacall_value_create_buffer(self.as_ptr().cast(), self.len()) }
+    }
+}
+/// Equivalent to Metacall buffer type.
+impl MetacallValue for MetacallBuffer {
+    fn get_metacall_id() -> u32 {
+        8
+    }
+    fn from_metacall_raw_leak(v: *mut c_void) -> Result<Self, Box<dyn MetacallValue>> {
+        // The value is owned by someone else (an array or a map for example) and may be destroyed
+        // before the buffer, so it gets copied
+        Ok(Self::new_raw(unsafe { metacall_value_copy(v) }))
+    }
+    fn from_metacall_raw(v: *mut c_void) -> Result<Self, Box<dyn MetacallValue>> {
+        Ok(Self::new_raw(v))
+    }
+    fn into_metacall_raw(self) -> *mut c_void {
+        self.into_raw()
     }
 }
 /// Equivalent to Metacall array type.
@@ -254,57 +400,76 @@ impl<T: MetacallValue + Clone> MetacallValue for Vec<T> {
         unsafe { metacall_value_create_array(array.as_mut_ptr(), array.len()) }
     }
 }
-/// Equivalent to Metacall map type.
-impl<T: MetacallValue + Clone> MetacallValue for HashMap<String, T> {
+fn map_from_raw<K: MetacallValue, V: MetacallValue>(
+    v: *mut c_void,
+) -> Result<Vec<(K, V)>, Box<dyn MetacallValue>> {
+    unsafe { slice::from_raw_parts(metacall_value_to_map(v), metacall_value_count(v)) }
+        .iter()
+        .map(|pair| {
+            let pair = unsafe { slice::from_raw_parts(metacall_value_to_array(*pair), 2) };
+
+            // Keys that don't fit are returned as casting errors instead of being skipped or
+            // merged with other keys
+            Ok((
+                parsers::raw_to_metacallobj_leak::<K>(pair[0])?,
+                parsers::raw_to_metacallobj_leak::<V>(pair[1])?,
+            ))
+        })
+        .collect()
+}
+fn map_into_raw<K: MetacallValue, V: MetacallValue>(
+    entries: impl IntoIterator<Item = (K, V)>,
+) -> *mut c_void {
+    let mut map: Vec<*const c_void> = entries
+        .into_iter()
+        .map(|(key, value)| {
+            let mut pair = [
+                key.into_metacall_raw().cast_const(),
+                value.into_metacall_raw().cast_const(),
+            ];
+
+            unsafe { metacall_value_create_array(pair.as_mut_ptr(), 2).cast_const() }
+        })
+        .collect();
+
+    unsafe { metacall_value_create_map(map.as_mut_ptr(), map.len()) }
+}
+/// Equivalent to Metacall map type. Every key must be of type `K`, otherwise the conversion fails.
+impl<K: MetacallValue + Clone + Hash + Eq, V: MetacallValue + Clone> MetacallValue
+    for HashMap<K, V>
+{
     fn get_metacall_id() -> u32 {
         10
     }
     fn from_metacall_raw_leak(v: *mut c_void) -> Result<Self, Box<dyn MetacallValue>> {
-        unsafe {
-            let mut hashmap = HashMap::new();
-            for map_value in
-                slice::from_raw_parts(metacall_value_to_map(v), metacall_value_count(v)).iter()
-            {
-                let m_pair = slice::from_raw_parts(metacall_value_to_array(*map_value), 2);
-                let key = match_metacall_value!(parsers::raw_to_metacallobj_untyped_leak(m_pair[0]), {
-                    str: String => str,
-                    num: i16 => num.to_string(),
-                    num: i32 => num.to_string(),
-                    num: i64 => num.to_string(),
-                    num: f32 => num.to_string(),
-                    num: f64 => num.to_string(),
-                    _ =>  String::from("Invalid key!")
-                });
-                let val = match parsers::raw_to_metacallobj_leak::<T>(m_pair[1]) {
-                    Ok(parsed) => parsed,
-                    Err(original) => {
-                        return Err(original);
-                    }
-                };
-
-                hashmap.insert(key, val);
-            }
-
-            Ok(hashmap)
-        }
+        Ok(map_from_raw(v)?.into_iter().collect())
     }
     fn into_metacall_raw(self) -> *mut c_void {
-        let mut hashmap: Vec<*const c_void> = self
-            .into_iter()
-            .map(|(key, value)| {
-                let key = key.into_metacall_raw();
-                let value = value.into_metacall_raw();
-
-                unsafe {
-                    metacall_value_create_array(
-                        vec![key as *const c_void, value as *const c_void].as_mut_ptr(),
-                        2,
-                    ) as *const c_void
-                }
-            })
-            .collect();
-
-        unsafe { metacall_value_create_map(hashmap.as_mut_ptr(), hashmap.len()) }
+        map_into_raw(self)
+    }
+}
+/// Equivalent to Metacall map type. Every key must be of type `K`, otherwise the conversion fails.
+impl<K: MetacallValue + Clone + Ord, V: MetacallValue + Clone> MetacallValue for BTreeMap<K, V> {
+    fn get_metacall_id() -> u32 {
+        10
+    }
+    fn from_metacall_raw_leak(v: *mut c_void) -> Result<Self, Box<dyn MetacallValue>> {
+        Ok(map_from_raw(v)?.into_iter().collect())
+    }
+    fn into_metacall_raw(self) -> *mut c_void {
+        map_into_raw(self)
+    }
+}
+/// Equivalent to Metacall map type, keeping the order of the entries.
+impl<K: MetacallValue + Clone, V: MetacallValue + Clone> MetacallValue for MetacallMap<K, V> {
+    fn get_metacall_id() -> u32 {
+        10
+    }
+    fn from_metacall_raw_leak(v: *mut c_void) -> Result<Self, Box<dyn MetacallValue>> {
+        Ok(MetacallMap::from(map_from_raw(v)?))
+    }
+    fn into_metacall_raw(self) -> *mut c_void {
+        map_into_raw(self)
     }
 }
 /// Equivalent to Metacall pointer type.
@@ -352,6 +517,54 @@ impl MetacallValue for MetacallFunction {
         self.into_raw()
     }
 }
+/// Any Metacall type, parsed into the matching [MetacallAny](MetacallAny) variant.
+impl MetacallValue for MetacallAny {
+    fn get_metacall_id() -> u32 {
+        // Null, so null return values are accepted too
+        14
+    }
+    fn accepts_metacall_id(_id: u32) -> bool {
+        true
+    }
+    fn from_metacall_raw_leak(v: *mut c_void) -> Result<Self, Box<dyn MetacallValue>> {
+        Ok(match unsafe { metacall_value_id(v) } {
+            9 => MetacallAny::Array(<Vec<MetacallAny>>::from_metacall_raw_leak(v)?),
+            10 => MetacallAny::Map(MetacallMap::from_metacall_raw_leak(v)?),
+            // Anything else than plain data refers to the value itself, which is owned by someone
+            // else (an array or a map for example), so it gets copied
+            11..=13 | 15..=18 => {
+                parsers::raw_to_metacallobj_untyped(unsafe { metacall_value_copy(v) }).into()
+            }
+            _ => parsers::raw_to_metacallobj_untyped_leak(v).into(),
+        })
+    }
+    fn from_metacall_raw(v: *mut c_void) -> Result<Self, Box<dyn MetacallValue>> {
+        Ok(parsers::raw_to_metacallobj_untyped(v).into())
+    }
+    fn into_metacall_raw(self) -> *mut c_void {
+        match self {
+            MetacallAny::Bool(value) => value.into_metacall_raw(),
+            MetacallAny::Char(value) => value.into_metacall_raw(),
+            MetacallAny::Short(value) => value.into_metacall_raw(),
+            MetacallAny::Int(value) => value.into_metacall_raw(),
+            MetacallAny::Long(value) => value.into_metacall_raw(),
+            MetacallAny::Float(value) => value.into_metacall_raw(),
+            MetacallAny::Double(value) => value.into_metacall_raw(),
+            MetacallAny::String(value) => value.into_metacall_raw(),
+            MetacallAny::Buffer(value) => value.into_metacall_raw(),
+            MetacallAny::Array(value) => value.into_metacall_raw(),
+            MetacallAny::Map(value) => value.into_metacall_raw(),
+            MetacallAny::Pointer(value) => value.into_metacall_raw(),
+            MetacallAny::Future(value) => value.into_metacall_raw(),
+            MetacallAny::Function(value) => value.into_metacall_raw(),
+            MetacallAny::Null => MetacallNull().into_metacall_raw(),
+            MetacallAny::Class(value) => value.into_metacall_raw(),
+            MetacallAny::Object(value) => value.into_metacall_raw(),
+            MetacallAny::Exception(value) => value.into_metacall_raw(),
+            MetacallAny::Throwable(value) => value.into_metacall_raw(),
+        }
+    }
+}
 /// Equivalent to Metacall null type.
 impl MetacallValue for MetacallNull {
     fn get_metacall_id() -> u32 {
@@ -429,3 +642,23 @@ impl MetacallValue for MetacallThrowable {
         self.into_raw()
     }
 }
+/// Trait object of any Metacall value. Useful for passing arguments of different types, checkout
+/// [metacall_args](crate::metacall_args) for more details.
+impl MetacallValue for Box<dyn MetacallValue> {
+    fn get_metacall_id() -> u32 {
+        // Null, so null return values are accepted too
+        14
+    }
+    fn accepts_metacall_id(_id: u32) -> bool {
+        true
+    }
+    fn from_metacall_raw_leak(v: *mut c_void) -> Result<Self, Box<dyn MetacallValue>> {
+        Ok(parsers::raw_to_metacallobj_untyped_leak(v))
+    }
+    fn from_metacall_raw(v: *mut c_void) -> Result<Self, Box<dyn MetacallValue>> {
+        Ok(parsers::raw_to_metacallobj_untyped(v))
+    }
+    fn into_metacall_raw(self) -> *mut c_void {
+        self.into_metacall_raw_box()
+    }
+}
@@ -1,18 +1,32 @@
+mod metacall_allocator;
+mod metacall_any;
+mod metacall_args;
+mod metacall_buffer;
 mod metacall_class;
+mod metacall_dispatcher;
 mod metacall_error;
 mod metacall_exception;
 mod metacall_function;
 mod metacall_future;
+mod metacall_handle;
+mod metacall_map;
 mod metacall_null;
 mod metacall_object;
 mod metacall_pointer;
 mod metacall_value;
 
+pub use metacall_allocator::*;
+pub use metacall_any::*;
+pub use metacall_args::*;
+pub use metacall_buffer::*;
 pub use metacall_class::*;
+pub use metacall_dispatcher::*;
 pub use metacall_error::*;
 pub use metacall_exception::*;
 pub use metacall_function::*;
 pub use metacall_future::*;
+pub use metacall_handle::*;
+pub use metacall_map::*;
 pub use metacall_null::*;
 pub use metacall_object::*;
 pub use metacall_pointer::*;
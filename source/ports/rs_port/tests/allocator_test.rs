@@ -0,0 +1,145 @@
+use metacall::{switch, MetacallAllocator, MetacallArena, MetacallValue};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    slice,
+    sync::{
+        atomic::{AtomicIsize, Ordering},
+        Arc,
+    },
+};
+
+// Counts the blocks alive, to check that the memory comes from Rust and goes back to it.
+struct CountingAllocator(Arc<AtomicIsize>);
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+// Counts the blocks given so far besides the ones alive, to check which values go through it.
+struct TotalAllocator {
+    alive: Arc<AtomicIsize>,
+    total: Arc<AtomicIsize>,
+}
+unsafe impl GlobalAlloc for TotalAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.total.fetch_add(1, Ordering::SeqCst);
+        self.alive.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.alive.fetch_sub(1, Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+fn fill_and_grow(allocator: &MetacallAllocator) {
+    let data = allocator.alloc(4) as *mut u8;
+    if data.is_null() {
+        panic!("Failed to allocate from the Metacall allocator!");
+    }
+    unsafe { slice::from_raw_parts_mut(data, 4) }.copy_from_slice(b"meta");
+
+    let data = unsafe { allocator.realloc(data.cast(), 4, 4096) } as *mut u8;
+    if data.is_null() {
+        panic!("Failed to reallocate from the Metacall allocator!");
+    }
+    let content = unsafe { slice::from_raw_parts(data, 4) };
+    if content != b"meta" {
+        panic!(
+            "Invalid reallocated content! Expected `meta` but received `{}`.",
+            String::from_utf8_lossy(content)
+        );
+    }
+
+    unsafe { allocator.free(data.cast()) };
+}
+
+#[test]
+fn allocator() {
+    fill_and_grow(&MetacallAllocator::std());
+
+    let blocks = Arc::new(AtomicIsize::new(0));
+    let allocator = MetacallAllocator::from_global_alloc(CountingAllocator(blocks.clone()));
+    // The core allocates both the allocator and its implementation from the Rust one
+    if blocks.load(Ordering::SeqCst) != 2 {
+        panic!(
+            "Invalid number of blocks! Expected `2` but received `{}`.",
+            blocks.load(Ordering::SeqCst)
+        );
+    }
+    fill_and_grow(&allocator);
+    drop(allocator);
+    if blocks.load(Ordering::SeqCst) != 0 {
+        panic!(
+            "Invalid number of blocks! Expected `0` but received `{}`.",
+            blocks.load(Ordering::SeqCst)
+        );
+    }
+
+    let arena = MetacallArena::with_chunk_size(1024);
+    let block = unsafe { arena.alloc(Layout::from_size_align(10, 8).unwrap()) };
+    let big_block = unsafe { arena.alloc(Layout::from_size_align(2048, 16).unwrap()) };
+    if block.is_null() || big_block.is_null() || !(big_block as usize).is_multiple_of(16) {
+        panic!("Failed to allocate aligned blocks from the arena!");
+    }
+    if arena.allocated() != 2058 {
+        panic!(
+            "Invalid allocated size! Expected `2058` but received `{}`.",
+            arena.allocated()
+        );
+    }
+
+    fill_and_grow(&MetacallAllocator::from_global_alloc(MetacallArena::new()));
+}
+
+#[test]
+fn allocator_scope() {
+    let _d = switch::initialize().unwrap();
+
+    let alive = Arc::new(AtomicIsize::new(0));
+    let total = Arc::new(AtomicIsize::new(0));
+    let allocator = MetacallAllocator::from_global_alloc(TotalAllocator {
+        alive: alive.clone(),
+        total: total.clone(),
+    });
+    let allocator_blocks = alive.load(Ordering::SeqCst);
+
+    // Values created outside of the scope don't go through the allocator
+    vec![1, 2, 3].to_json().unwrap();
+    if total.load(Ordering::SeqCst) != allocator_blocks {
+        panic!(
+            "Invalid number of blocks! Expected `{}` but received `{}`.",
+            allocator_blocks,
+            total.load(Ordering::SeqCst)
+        );
+    }
+
+    // The array and its elements are created in the scope, and destroyed before leaving it
+    let json = unsafe { allocator.scope(|| vec![1, 2, 3].to_json()) }.unwrap();
+    if json.as_str() != "[1,2,3]" {
+        panic!(
+            "Invalid serialized value! Expected `[1,2,3]` but received `{}`.",
+            json
+        );
+    }
+    if total.load(Ordering::SeqCst) < allocator_blocks + 4 {
+        panic!(
+            "Invalid number of blocks! Expected at least `{}` but received `{}`.",
+            allocator_blocks + 4,
+            total.load(Ordering::SeqCst)
+        );
+    }
+    if alive.load(Ordering::SeqCst) != allocator_blocks {
+        panic!(
+            "Invalid number of blocks! Expected `{}` but received `{}`.",
+            allocator_blocks,
+            alive.load(Ordering::SeqCst)
+        );
+    }
+}
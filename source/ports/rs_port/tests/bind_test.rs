@@ -0,0 +1,66 @@
+use metacall::{loaders, switch};
+use std::thread;
+
+#[metacall::bind(tag = "py", file = "tests/scripts/script.py")]
+extern "C" {
+    fn return_the_argument_py(argument: String) -> String;
+    fn return_the_arguments_py(
+        first: i64,
+        second: String,
+        third: bool,
+    ) -> Vec<Box<dyn metacall::MetacallValue>>;
+}
+
+#[test]
+fn bind() {
+    let d = switch::initialize().unwrap();
+
+    // Skips the test when the Python loader isn't available
+    if loaders::from_memory("py", "pass").is_ok() {
+        // Calling it twice makes sure the cached function is reused
+        for _ in 0..2 {
+            let ret = return_the_argument_py(String::from("hi there!")).unwrap();
+            if ret.as_str() != "hi there!" {
+                panic!(
+                    "Invalid return value! Expected `hi there!` but received `{}`.",
+                    ret
+                );
+            }
+        }
+
+        let ret = return_the_arguments_py(1, String::from("a"), true).unwrap();
+        if ret.len() != 3 {
+            panic!(
+                "Invalid return value! Expected 3 values but received `{:#?}`.",
+                ret
+            );
+        }
+
+        // The handle is shared between threads, so the script isn't loaded again by other threads
+        thread::scope(|scope| {
+            for _ in 0..2 {
+                scope.spawn(|| {
+                    let ret = return_the_argument_py(String::from("hi there!")).unwrap();
+                    if ret.as_str() != "hi there!" {
+                        panic!(
+                            "Invalid return value! Expected `hi there!` but received `{}`.",
+                            ret
+                        );
+                    }
+                });
+            }
+        });
+
+        // The cached handle and function belong to the destroyed runtime, so they're loaded again
+        drop(d);
+        let _d = switch::initialize().unwrap();
+
+        let ret = return_the_argument_py(String::from("hi there!")).unwrap();
+        if ret.as_str() != "hi there!" {
+            panic!(
+                "Invalid return value! Expected `hi there!` but received `{}`.",
+                ret
+            );
+        }
+    }
+}
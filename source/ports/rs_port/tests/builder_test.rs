@@ -0,0 +1,27 @@
+use metacall::{loaders, MetacallBuilder, MetacallLog};
+use std::env;
+
+// The script imports a module from the execution path instead of loading it by its path
+const SCRIPT: &str = "from script import return_the_argument_py\n\
+def return_from_execution_path():\n    return return_the_argument_py('hi there!')\n";
+
+#[test]
+fn builder() {
+    let scripts_dir = env::current_dir().unwrap().join("tests/scripts");
+    let _d = MetacallBuilder::new()
+        .log(MetacallLog::Stdout)
+        .args(["builder_test"])
+        .execution_path("py", scripts_dir)
+        .initialize()
+        .unwrap();
+
+    if loaders::from_memory("py", SCRIPT).is_ok() {
+        let ret = metacall::metacall_no_arg::<String>("return_from_execution_path").unwrap();
+        if ret.as_str() != "hi there!" {
+            panic!(
+                "Invalid return value! Expected `hi there!` but received `{}`.",
+                ret
+            );
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use metacall::{
+    loaders, metacall_no_arg, MetacallDispatcher, MetacallDispatcherError, MetacallObject,
+    MetacallSendable,
+};
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake},
+    thread::{self, Thread},
+};
+
+const SCRIPT: &str = "class Greeter:\n    hi = 'there!'\n\n\
+def dispatcher_greet(name):\n    return 'hi ' + name\n\
+def dispatcher_greeter():\n    return Greeter()\n";
+const ASYNC_SCRIPT: &str = "module.exports = {\n\
+    dispatcher_await_greet: async (name) => 'hi ' + name,\n\
+    dispatcher_await_reject: async () => { throw new Error('oops'); },\n\
+};\n";
+
+struct ThreadWaker(Thread);
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Arc::new(ThreadWaker(thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[test]
+fn dispatcher() {
+    let dispatcher = MetacallDispatcher::new().unwrap();
+    let client = dispatcher.client();
+
+    if client
+        .run(|| loaders::from_memory("py", SCRIPT))
+        .unwrap()
+        .is_err()
+    {
+        return;
+    }
+
+    // Calls from many threads are run one by one in the dispatcher thread
+    let workers: Vec<_> = (0..4)
+        .map(|i| {
+            let client = client.clone();
+
+            thread::spawn(move || {
+                client
+                    .call::<String>("dispatcher_greet", [i.to_string()])
+                    .unwrap()
+            })
+        })
+        .collect();
+    for (i, worker) in workers.into_iter().enumerate() {
+        let ret = worker.join().unwrap();
+        if ret != format!("hi {}", i) {
+            panic!(
+                "Invalid return value! Expected `hi {}` but received `{}`.",
+                i, ret
+            );
+        }
+    }
+
+    // Objects are moved out of the dispatcher thread wrapped, and used back in it
+    let object = client
+        .run(|| {
+            MetacallSendable::new(metacall_no_arg::<MetacallObject>("dispatcher_greeter").unwrap())
+        })
+        .unwrap();
+    if object.get().is_some() {
+        panic!("The wrapped object must not be accessible outside of the dispatcher thread!");
+    }
+    let ret = client
+        .run(move || object.get().unwrap().get_attribute::<String>("hi").unwrap())
+        .unwrap();
+    if ret.as_str() != "there!" {
+        panic!(
+            "Invalid return value! Expected `there!` but received `{}`.",
+            ret
+        );
+    }
+
+    if !matches!(
+        client.call_no_arg::<String>("dispatcher_missing"),
+        Err(MetacallDispatcherError::CallErr { .. })
+    ) {
+        panic!("Calling a missing function must fail with `CallErr`!");
+    }
+
+    if client
+        .run(|| loaders::from_memory("node", ASYNC_SCRIPT))
+        .unwrap()
+        .is_ok()
+    {
+        // The futures of the client are awaited in other threads
+        let future = client.call_async::<String>("dispatcher_await_greet", [String::from("a")]);
+        let ret = thread::spawn(move || block_on(future))
+            .join()
+            .unwrap()
+            .unwrap();
+        if ret.as_str() != "hi a" {
+            panic!(
+                "Invalid return value! Expected `hi a` but received `{}`.",
+                ret
+            );
+        }
+
+        let future = client.call_async_no_arg::<String>("dispatcher_await_reject");
+        if !matches!(
+            thread::spawn(move || block_on(future)).join().unwrap(),
+            Err(MetacallDispatcherError::CallErr { .. })
+        ) {
+            panic!("Awaiting a rejected function must fail with `CallErr`!");
+        }
+    }
+
+    drop(dispatcher);
+
+    if !matches!(
+        client.call::<String>("dispatcher_greet", [String::from("a")]),
+        Err(MetacallDispatcherError::Stopped)
+    ) {
+        panic!("Calling through a stopped dispatcher must fail with `Stopped`!");
+    }
+    if !matches!(
+        block_on(client.call_async_no_arg::<String>("dispatcher_await_greet")),
+        Err(MetacallDispatcherError::Stopped)
+    ) {
+        panic!("Awaiting through a stopped dispatcher must fail with `Stopped`!");
+    }
+}
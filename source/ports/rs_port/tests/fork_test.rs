@@ -0,0 +1,59 @@
+use metacall::{loaders, switch, MetacallError, MetacallFunction};
+use std::sync::{
+    atomic::{AtomicBool, AtomicI32, Ordering},
+    Arc,
+};
+
+const SCRIPT: &str = "def fork_greet():\n    return 'hi there!'\n";
+
+#[test]
+fn fork() {
+    let _d = switch::initialize().unwrap();
+
+    if loaders::from_memory("py", SCRIPT).is_err() {
+        return;
+    }
+    let greet = MetacallFunction::from_name("fork_greet").unwrap();
+
+    let pre_called = Arc::new(AtomicBool::new(false));
+    let post_pid = Arc::new(AtomicI32::new(-1));
+    let pid = {
+        let pre_called = pre_called.clone();
+        let post_pid = post_pid.clone();
+
+        metacall::fork(
+            move || pre_called.store(true, Ordering::SeqCst),
+            move |pid| {
+                // The runtime is initialized again, so the script must be loaded again
+                loaders::from_memory("py", SCRIPT).unwrap();
+                post_pid.store(pid, Ordering::SeqCst);
+            },
+        )
+        .unwrap()
+    };
+
+    if pid == 0 {
+        // Nothing else must run in the child
+        unsafe { metacall::bindings::_exit(0) };
+    }
+
+    if !pre_called.load(Ordering::SeqCst) || post_pid.load(Ordering::SeqCst) != pid {
+        panic!("The fork callbacks must be called before returning!");
+    }
+
+    // The function belongs to the runtime destroyed before forking
+    if !matches!(
+        greet.call_no_arg::<String>(),
+        Err(MetacallError::NotInitialized)
+    ) {
+        panic!("Calling a function of the previous runtime must fail with `NotInitialized`!");
+    }
+
+    let ret = metacall::metacall_no_arg::<String>("fork_greet").unwrap();
+    if ret.as_str() != "hi there!" {
+        panic!(
+            "Invalid return value! Expected `hi there!` but received `{}`.",
+            ret
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use metacall::{
+    inline::{include_node, include_py},
+    loaders, metacall, switch,
+};
+
+#[test]
+fn includes() {
+    let _d = switch::initialize().unwrap();
+
+    // Skips the Python checks when the Python loader isn't available
+    if loaders::from_memory("py", "pass").is_ok() {
+        let handle = include_py!("scripts/script.py", handle).unwrap();
+        let ret = handle
+            .call::<String>("return_the_argument_py", [String::from("hi there!")])
+            .unwrap();
+        if ret.as_str() != "hi there!" {
+            panic!(
+                "Invalid return value! Expected `hi there!` but received `{}`.",
+                ret
+            );
+        }
+        // Unloading the handle first, so the same script can be loaded again into the global scope
+        drop(handle);
+
+        include_py!("scripts/script.py").unwrap();
+
+        let ret =
+            metacall::<String>("return_the_argument_py", [String::from("hi there!")]).unwrap();
+        if ret.as_str() != "hi there!" {
+            panic!(
+                "Invalid return value! Expected `hi there!` but received `{}`.",
+                ret
+            );
+        }
+    }
+
+    // Skips the NodeJS checks when the NodeJS loader isn't available
+    if loaders::from_memory("node", "module.exports = {};").is_ok() {
+        let handle = include_node!("scripts/script.js", handle).unwrap();
+        if !handle
+            .exports()
+            .contains(&String::from("return_the_argument_js"))
+        {
+            panic!(
+                "Expected `return_the_argument_js` to be exported but received `{:#?}`.",
+                handle.exports()
+            );
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use metacall::{inspect, loaders, switch};
+use std::env;
+
+#[test]
+fn inspect() {
+    let _d = switch::initialize().unwrap();
+
+    let tests_dir = env::current_dir().unwrap().join("tests/scripts");
+    let py_test_file = tests_dir.join("script.py");
+
+    if loaders::from_single_file("py", py_test_file).is_ok() {
+        let loaders = inspect::loaders().unwrap();
+        let py_loader = loaders
+            .iter()
+            .find(|loader| loader.tag == "py")
+            .expect("Expected the inspect output to contain the `py` loader!");
+
+        if !py_loader
+            .handles
+            .iter()
+            .any(|handle| handle.classes.iter().any(|class| class.name == "TestClass"))
+        {
+            panic!("Expected the `py` loader to export `TestClass`!");
+        }
+
+        let function = inspect::function("return_the_argument_py")
+            .unwrap()
+            .expect("Expected `return_the_argument_py` to be found!");
+        let parameters = function
+            .parameters
+            .iter()
+            .map(|parameter| parameter.name.as_str())
+            .collect::<Vec<&str>>();
+
+        if parameters != ["argument"] || function.is_async {
+            panic!(
+                "Invalid signature! Expected a sync function with `[\"argument\"]` parameters but received `{:#?}`.",
+                function
+            );
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use metacall::{loaders, metacall_no_arg, switch};
+use metacall::{loaders, metacall_no_arg, switch, MetacallLoaderError};
 use std::{
     env,
     fs::{self, File},
@@ -10,6 +10,10 @@ use std::{
 // in a single test.
 const SCRIPT1: &str = "function greet1() { return 'hi there!' } \nmodule.exports = { greet1 };";
 const SCRIPT2: &str = "function greet2() { return 'hi there!' } \nmodule.exports = { greet2 };";
+const SCRIPT3: &str = "function greet() { return 'hi there!' } \nmodule.exports = { greet };";
+const SCRIPT4: &str = "function greet() { return 'hello there!' } \nmodule.exports = { greet };";
+const SCRIPT5: &str = "function greet5() { return 'hi there!' } \nmodule.exports = { greet5 };";
+const CONFIGURATION: &str = r#"{ "language_id": "node", "path": ".", "scripts": ["greet5.js"] }"#;
 
 fn call_greet(test: &str, num: u32) {
     let out = metacall_no_arg::<String>(format!("greet{}", num)).unwrap();
@@ -29,6 +33,32 @@ fn load_from_memory_test() {
     call_greet("load_from_memory", 1);
 }
 
+fn load_from_memory_handle_test() {
+    // Both scripts export the same name, which only works because each one has its own handle
+    let handle1 = loaders::from_memory_handle("node", SCRIPT3).unwrap();
+    let handle2 = loaders::from_memory_handle("node", SCRIPT4).unwrap();
+
+    if handle1.exports() != vec![String::from("greet")] {
+        panic!(
+            "Invalid exports of the handle! Expected `[\"greet\"]` but received `{:?}`.",
+            handle1.exports()
+        );
+    }
+
+    let out1 = handle1.call_no_arg::<String>("greet").unwrap();
+    let out2 = handle2
+        .get_function("greet")
+        .unwrap()
+        .call_no_arg::<String>()
+        .unwrap();
+    if out1.as_str() != "hi there!" || out2.as_str() != "hello there!" {
+        panic!(
+            "Invalid output of the handles! Expected `hi there!` and `hello there!` but received `{}` and `{}`.",
+            out1, out2
+        );
+    }
+}
+
 fn load_from_file_test() {
     // Finding a temporary address to store the temporary js file
     let temp_js_pathbuf =
@@ -48,6 +78,39 @@ fn load_from_file_test() {
     fs::remove_file(temp_js_pathbuf).unwrap();
 }
 
+fn load_from_configuration_test() {
+    let temp_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("target/tmp");
+    let temp_js_pathbuf = temp_dir.join("greet5.js");
+    let temp_json_pathbuf = temp_dir.join("greet5.json");
+
+    fs::write(&temp_js_pathbuf, SCRIPT5).unwrap();
+    fs::write(&temp_json_pathbuf, CONFIGURATION).unwrap();
+
+    // The script is found relative to the configuration file
+    loaders::from_configuration(&temp_json_pathbuf).unwrap();
+
+    call_greet("load_from_configuration", 5);
+
+    if !matches!(
+        loaders::from_configuration(temp_dir.join("missing.json")),
+        Err(MetacallLoaderError::FileNotFound(_))
+    ) {
+        panic!("Loading a missing configuration must fail with `FileNotFound`!");
+    }
+
+    fs::remove_file(temp_js_pathbuf).unwrap();
+    fs::remove_file(temp_json_pathbuf).unwrap();
+}
+
+fn load_from_package_test() {
+    if !matches!(
+        loaders::from_package("node", "missing_package.node"),
+        Err(MetacallLoaderError::FromPackageFailure)
+    ) {
+        panic!("Loading a missing package must fail with `FromPackageFailure`!");
+    }
+}
+
 #[test]
 fn loaders() {
     let _d = switch::initialize().unwrap();
@@ -57,4 +120,13 @@ fn loaders() {
 
     // Testing load_from_files
     load_from_file_test();
+
+    // Testing load_from_memory into handles
+    load_from_memory_handle_test();
+
+    // Testing load_from_configuration
+    load_from_configuration_test();
+
+    // Testing load_from_package
+    load_from_package_test();
 }
@@ -1,8 +1,18 @@
 use metacall::{
-    loaders, switch, MetacallClass, MetacallException, MetacallFunction, MetacallFuture,
-    MetacallNull, MetacallObject, MetacallPointer, MetacallThrowable, MetacallValue,
+    loaders, metacall_args, switch, MetacallAny, MetacallArgsIter, MetacallBuffer, MetacallClass,
+    MetacallError, MetacallException, MetacallFunction, MetacallFuture, MetacallMap, MetacallNull,
+    MetacallObject, MetacallPointer, MetacallThrowable, MetacallValue,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    fmt::Debug,
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake},
+    thread::{self, Thread},
 };
-use std::{collections::HashMap, env, fmt::Debug};
 
 fn generate_test<T: MetacallValue + PartialEq + Debug + Clone>(
     name: impl ToString,
@@ -44,6 +54,26 @@ fn generate_test_custom_validation<T: MetacallValue + Debug>(
     };
 }
 
+// Minimal executor for awaiting futures without pulling an async runtime
+struct ThreadWaker(Thread);
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Arc::new(ThreadWaker(thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
 fn invalid_return_type(expected: impl Debug, received: impl Debug) {
     panic!(
         "Invalid return type! Expected: `{:#?}` but received `{:#?}`.",
@@ -74,6 +104,31 @@ fn test_int() {
 fn test_long() {
     generate_test::<i64>("test_long", 12345 as i64);
 }
+fn test_wide_integers() {
+    generate_test::<u8>("return_the_argument_py", u8::MAX);
+    generate_test::<u16>("return_the_argument_py", u16::MAX);
+    generate_test::<u32>("return_the_argument_py", u32::MAX);
+    generate_test::<u64>("return_the_argument_py", i64::MAX as u64);
+    generate_test::<usize>("return_the_argument_py", 12345);
+    generate_test::<i128>("return_the_argument_py", i64::MIN as i128);
+
+    match metacall::metacall::<u64>("return_the_argument_py", [-1]) {
+        Err(MetacallError::FailedCasting(_)) => (),
+        ret => invalid_return_type("casting error", ret),
+    }
+    match metacall::metacall::<u64>("return_the_argument_py", [u64::MAX]) {
+        Err(MetacallError::FailedConversion(_)) => (),
+        ret => invalid_return_type("conversion error", ret),
+    }
+    match metacall::metacall::<i64>("return_the_argument_py", [vec![1, u64::MAX]]) {
+        Err(MetacallError::FailedConversion(_)) => (),
+        ret => invalid_return_type("conversion error", ret),
+    }
+}
+fn test_unicode_char() {
+    generate_test::<char>("return_the_argument_py", 'é');
+    generate_test::<char>("return_the_argument_py", '🦀');
+}
 fn test_float() {
     generate_test::<f32>("test_float", 1.2345 as f32);
 }
@@ -96,6 +151,20 @@ fn test_buffer() {
             .collect(),
     );
 }
+fn test_metacall_buffer() {
+    let expected = MetacallBuffer::from("hi there!".as_bytes());
+    generate_test::<MetacallBuffer>("return_the_argument_py", expected.clone());
+
+    let mut zeroed = MetacallBuffer::zeroed(expected.len());
+    zeroed.copy_from_slice(&expected);
+    let ret = ::metacall::metacall::<MetacallBuffer>("return_the_argument_py", [zeroed]).unwrap();
+    if ret.as_slice() != "hi there!".as_bytes() {
+        invalid_return_value("hi there!".as_bytes(), ret.as_slice());
+    }
+    if Vec::from(ret) != b"hi there!".to_vec() {
+        panic!("Expected the buffer to be converted into the same bytes");
+    }
+}
 fn test_map() {
     let mut expected_hashmap = HashMap::new();
     expected_hashmap.insert(String::from("hi"), String::from("there!"));
@@ -118,6 +187,47 @@ fn test_map() {
         },
     );
 }
+fn test_map_keys() {
+    let mut expected_btreemap: BTreeMap<i64, String> = BTreeMap::new();
+    expected_btreemap.insert(1, String::from("one"));
+    expected_btreemap.insert(2, String::from("two"));
+    generate_test::<BTreeMap<i64, String>>("return_the_argument_py", expected_btreemap.clone());
+
+    match ::metacall::metacall::<HashMap<String, String>>(
+        "return_the_argument_py",
+        [expected_btreemap],
+    ) {
+        Err(MetacallError::FailedCasting(_)) => (),
+        ret => invalid_return_type("a casting error for the integer keys", ret),
+    }
+}
+fn test_ordered_map() {
+    let expected_map: MetacallMap<String, i64> = MetacallMap::from(vec![
+        (String::from("c"), 3),
+        (String::from("a"), 1),
+        (String::from("b"), 2),
+    ]);
+    generate_test::<MetacallMap<String, i64>>("return_the_argument_py", expected_map);
+}
+fn test_any() {
+    let expected = MetacallAny::Array(vec![
+        MetacallAny::Map(MetacallMap::from(vec![(
+            MetacallAny::String(String::from("list")),
+            MetacallAny::Array(vec![MetacallAny::Long(1), MetacallAny::Long(2)]),
+        )])),
+        MetacallAny::String(String::from("hi there!")),
+        MetacallAny::Null,
+    ]);
+    generate_test::<MetacallAny>("return_the_argument_py", expected.clone());
+
+    let untyped: MetacallAny =
+        ::metacall::metacall_untyped("return_the_argument_py", [expected.clone()])
+            .unwrap()
+            .into();
+    if untyped != expected {
+        invalid_return_value(expected, untyped);
+    }
+}
 fn test_array() {
     let expected_array = vec![String::from("hi"), String::from("there!")];
 
@@ -154,16 +264,9 @@ fn test_pointer() {
     );
 }
 fn test_future() {
-    fn validate(upper_result: Box<dyn MetacallValue>, upper_data: Box<dyn MetacallValue>) {
-        match upper_data.downcast::<String>() {
-            Ok(ret) => {
-                if ret.as_str() != "data" {
-                    invalid_return_value("data", ret)
-                }
-            }
-            Err(original) => {
-                invalid_return_type("'string' for the data", original);
-            }
+    fn validate(upper_result: Box<dyn MetacallValue>, upper_data: String) {
+        if upper_data.as_str() != "data" {
+            invalid_return_value("data", upper_data)
         }
 
         match upper_result.downcast::<String>() {
@@ -183,11 +286,15 @@ fn test_future() {
         "future",
         MetacallNull(),
         move |future| {
-            fn resolve(result: Box<dyn MetacallValue>, data: Box<dyn MetacallValue>) {
-                validate(result, data);
-            }
+            let data = String::from("data");
+
+            future
+                .then(move |result| {
+                    validate(result, data);
 
-            future.then(resolve).data(String::from("data")).await_fut();
+                    MetacallNull()
+                })
+                .await_fut();
         },
     );
     generate_test_custom_validation::<MetacallFuture>(
@@ -195,11 +302,38 @@ fn test_future() {
         "future",
         MetacallNull(),
         move |future| {
-            fn reject(result: Box<dyn MetacallValue>, data: Box<dyn MetacallValue>) {
-                validate(result, data);
+            let data = String::from("data");
+
+            future
+                .catch(move |result| {
+                    validate(result, data);
+
+                    MetacallNull()
+                })
+                .await_fut();
+        },
+    );
+
+    fn validate_native(result: Box<dyn MetacallValue>) {
+        match result.downcast::<String>() {
+            Ok(ret) => {
+                if ret.as_str() != "hi there!" {
+                    invalid_return_value("hi there!", ret)
+                }
+            }
+            Err(original) => {
+                invalid_return_type("'string' for the result", original);
             }
+        }
+    }
 
-            future.catch(reject).data(String::from("data")).await_fut();
+    generate_test_custom_validation::<MetacallFuture>(
+        "test_future_resolve",
+        "future",
+        MetacallNull(),
+        |future| match block_on(future) {
+            Ok(result) => validate_native(result),
+            Err(result) => invalid_return_value("resolved future", result),
         },
     );
 }
@@ -224,6 +358,129 @@ fn test_function() {
         },
     );
 }
+fn test_function_info() {
+    let function = MetacallFunction::from_name("call_the_callback_js").unwrap();
+
+    if function.name().as_deref() != Some("call_the_callback_js") {
+        invalid_return_value("call_the_callback_js", function.name());
+    }
+    if function.arity() != 2 {
+        invalid_return_value(2, function.arity());
+    }
+    if function.parameter_types() != vec![None, None] {
+        invalid_return_value("untyped parameters", function.parameter_types());
+    }
+    if function.is_async() {
+        invalid_return_value("synchronous function", "asynchronous function");
+    }
+}
+fn test_function_value_call() {
+    // Functions received as values must get the arguments passed to them
+    let function = MetacallFunction::from_name("return_the_arguments_py").unwrap();
+    let function =
+        metacall::metacall::<MetacallFunction>("return_the_argument_py", [function]).unwrap();
+
+    let args = [String::from("a"), String::from("b"), String::from("c")];
+    match function.call::<Vec<String>>(args.clone()) {
+        Ok(ret) if ret == args => (),
+        ret => invalid_return_value(args, ret),
+    }
+}
+fn test_iterator_args() {
+    let letters = ["a", "b", "c"];
+    let args = MetacallArgsIter(letters.iter().map(|letter| letter.to_uppercase()));
+    match metacall::metacall::<Vec<String>>("return_the_arguments_py", args) {
+        Ok(ret) if ret == ["A", "B", "C"] => (),
+        ret => invalid_return_value(["A", "B", "C"], ret),
+    }
+}
+fn test_await_call() {
+    let function = MetacallFunction::from_name("await_the_argument_js").unwrap();
+    if !function.is_async() {
+        invalid_return_value("asynchronous function", "synchronous function");
+    }
+
+    let ret = block_on(function.await_call::<String>([String::from("hi there!")])).unwrap();
+    if ret.as_str() != "hi there!" {
+        invalid_return_value("hi there!", ret);
+    }
+
+    let ret = block_on(metacall::metacall_async::<String>(
+        "await_the_argument_js",
+        [String::from("hi there!")],
+    ))
+    .unwrap();
+    if ret.as_str() != "hi there!" {
+        invalid_return_value("hi there!", ret);
+    }
+
+    let function = MetacallFunction::from_name("reject_the_argument_js").unwrap();
+    match block_on(function.await_call::<String>([String::from("hi there!")])) {
+        Err(MetacallError::Rejected(rejected)) => {
+            if rejected.downcast_ref::<String>().map(String::as_str) != Some("hi there!") {
+                invalid_return_value("hi there!", rejected);
+            }
+        }
+        ret => invalid_return_type("a rejection", ret),
+    }
+}
+fn test_heterogeneous_args() {
+    fn validate(ret: Vec<Box<dyn MetacallValue>>) {
+        let (first, second, third) = match ret.as_slice() {
+            [first, second, third] => (first, second, third),
+            _ => return invalid_return_value("3 values", ret),
+        };
+
+        if first.downcast_ref::<i64>() != Some(&1) {
+            invalid_return_value(1, first);
+        }
+        if second.downcast_ref::<String>().map(String::as_str) != Some("a") {
+            invalid_return_value("a", second);
+        }
+        if third.downcast_ref::<bool>() != Some(&true) {
+            invalid_return_value(true, third);
+        }
+    }
+
+    validate(
+        metacall::metacall::<Vec<Box<dyn MetacallValue>>>(
+            "return_the_arguments_py",
+            (1, String::from("a"), true),
+        )
+        .unwrap(),
+    );
+    validate(
+        metacall::metacall::<Vec<Box<dyn MetacallValue>>>(
+            "return_the_arguments_py",
+            metacall_args![1, String::from("a"), true],
+        )
+        .unwrap(),
+    );
+}
+fn test_closure() {
+    fn validate(args: Vec<Box<dyn MetacallValue>>) -> String {
+        match args.as_slice() {
+            [arg] => match arg.downcast_ref::<String>() {
+                Some(arg) => format!("{} there!", arg),
+                None => String::from("invalid argument"),
+            },
+            _ => String::from("invalid arguments"),
+        }
+    }
+
+    let callback = MetacallFunction::from_closure(validate).unwrap();
+    let ret = metacall::metacall::<String>("call_the_callback_js", (callback, String::from("hi")))
+        .unwrap();
+    if ret.as_str() != "hi there!" {
+        invalid_return_value("hi there!", ret);
+    }
+
+    metacall::register("registered_closure", validate).unwrap();
+    let ret = metacall::metacall::<String>("registered_closure", [String::from("hi")]).unwrap();
+    if ret.as_str() != "hi there!" {
+        invalid_return_value("hi there!", ret);
+    }
+}
 fn test_null() {
     metacall::metacall::<MetacallNull>("return_the_argument_py", [MetacallNull()]).unwrap();
 }
@@ -245,6 +502,10 @@ fn class_test_inner(class: MetacallClass) {
     if ret != "there!" {
         invalid_return_value("there!", ret)
     }
+    let ret = block_on(class.call_method_async_no_arg::<String>("hi_function")).unwrap();
+    if ret != "there!" {
+        invalid_return_value("there!", ret)
+    }
 
     let new_obj = class.create_object_no_arg("test_class_obj").unwrap();
     object_test_inner(new_obj);
@@ -280,6 +541,10 @@ fn object_test_inner(object: MetacallObject) {
     } else {
         invalid_return_value("there!", ret);
     }
+    let ret = block_on(object.call_method_async_no_arg::<String>("get_hi")).unwrap();
+    if ret.as_str() != "there!" {
+        invalid_return_value("there!", ret);
+    }
 }
 fn test_object() {
     generate_test_custom_validation::<MetacallObject>(
@@ -332,6 +597,16 @@ fn test_throwable() {
         },
     );
 }
+fn test_thrown() {
+    match metacall::metacall_no_arg::<String>("test_throwable") {
+        Err(MetacallError::Thrown(exception)) => {
+            if exception.get_message().as_str() != "hi there!" {
+                invalid_return_value("hi there!", exception.get_message());
+            }
+        }
+        ret => invalid_return_type("thrown exception", ret),
+    }
+}
 
 #[test]
 fn metacall() {
@@ -343,10 +618,19 @@ fn metacall() {
     let py_test_file = tests_dir.join("script.py");
 
     if let Ok(_) = loaders::from_single_file("py", py_test_file) {
+        test_any();
         test_buffer();
         test_class();
+        test_function_value_call();
+        test_iterator_args();
+        test_heterogeneous_args();
+        test_map_keys();
+        test_metacall_buffer();
         test_object();
+        test_ordered_map();
         test_pointer();
+        test_unicode_char();
+        test_wide_integers();
     }
     if let Ok(_) = loaders::from_single_file("c", c_test_file) {
         test_char();
@@ -358,13 +642,17 @@ fn metacall() {
     }
     if let Ok(_) = loaders::from_single_file("node", js_test_file) {
         test_array();
+        test_await_call();
         test_bool();
+        test_closure();
         test_exception();
         test_function();
+        test_function_info();
         test_map();
         test_null();
         test_string();
         test_throwable();
+        test_thrown();
         test_future();
     }
 }
@@ -0,0 +1,68 @@
+use metacall::{
+    initialize, loaders, metacall_no_arg, MetacallBuilder, MetacallBuilderError, MetacallError,
+    MetacallFunction, MetacallLoaderError,
+};
+
+const SCRIPT: &str = "def runtime_greet():\n    return 'hi there!'\n";
+
+#[test]
+fn runtime() {
+    // Nothing can be called before initializing
+    if !matches!(
+        metacall_no_arg::<String>("runtime_greet"),
+        Err(MetacallError::NotInitialized)
+    ) {
+        panic!("Calling a function before initializing must fail with `NotInitialized`!");
+    }
+    if !matches!(
+        loaders::from_memory("py", SCRIPT),
+        Err(MetacallLoaderError::NotInitialized)
+    ) {
+        panic!("Loading a script before initializing must fail with `NotInitialized`!");
+    }
+
+    let first = initialize().unwrap();
+    // Guards share the same runtime, so initializing again doesn't initialize it twice
+    let second = initialize().unwrap();
+    let third = first.clone();
+
+    if !matches!(
+        MetacallBuilder::new().initialize(),
+        Err(MetacallBuilderError::AlreadyInitialized)
+    ) {
+        panic!("Configuring an initialized runtime must fail with `AlreadyInitialized`!");
+    }
+
+    if loaders::from_memory("py", SCRIPT).is_err() {
+        return;
+    }
+
+    let greet = MetacallFunction::from_name("runtime_greet").unwrap();
+    drop(first);
+    drop(second);
+
+    // The runtime is still alive as there's a guard left
+    let ret = greet.call_no_arg::<String>().unwrap();
+    if ret.as_str() != "hi there!" {
+        panic!(
+            "Invalid return value! Expected `hi there!` but received `{}`.",
+            ret
+        );
+    }
+
+    drop(third);
+
+    // The function belongs to the destroyed runtime
+    if !matches!(
+        greet.call_no_arg::<String>(),
+        Err(MetacallError::NotInitialized)
+    ) {
+        panic!("Calling a function of a destroyed runtime must fail with `NotInitialized`!");
+    }
+    if !matches!(
+        metacall_no_arg::<String>("runtime_greet"),
+        Err(MetacallError::NotInitialized)
+    ) {
+        panic!("Calling a function after destroying must fail with `NotInitialized`!");
+    }
+}
@@ -16,8 +16,20 @@ function test_throwable() {
 function return_the_argument_js(argument) {
   return argument;
 }
+async function await_the_argument_js(argument) {
+  return argument;
+}
+async function reject_the_argument_js(argument) {
+  throw argument;
+}
+function call_the_callback_js(callback, argument) {
+  return callback(argument);
+}
 
 module.exports = {
+  await_the_argument_js,
+  call_the_callback_js,
+  reject_the_argument_js,
   return_the_argument_js,
   test_exception,
   test_function,
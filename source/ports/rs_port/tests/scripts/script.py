@@ -13,4 +13,6 @@ def test_class():
 def test_object():
     return TestClass()
 def return_the_argument_py(argument):
-    return argument
\ No newline at end of file
+    return argument
+def return_the_arguments_py(first, second, third):
+    return [first, second, third]
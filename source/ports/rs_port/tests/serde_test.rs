@@ -0,0 +1,74 @@
+#![cfg(feature = "serde")]
+
+use metacall::{loaders, metacall, metacall_serde, switch, MetacallError};
+use serde::{Deserialize, Serialize};
+use std::env;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Role {
+    Admin,
+    Guest { expires: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct User {
+    name: String,
+    age: u8,
+    tags: Vec<String>,
+    nickname: Option<String>,
+    role: Role,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Order {
+    id: u64,
+}
+
+metacall_serde!(User, Order);
+
+#[test]
+fn serde() {
+    let _d = switch::initialize().unwrap();
+
+    let tests_dir = env::current_dir().unwrap().join("tests/scripts");
+    let py_test_file = tests_dir.join("script.py");
+
+    if loaders::from_single_file("py", py_test_file).is_ok() {
+        let users = [
+            User {
+                name: String::from("Jane"),
+                age: 32,
+                tags: vec![String::from("rust"), String::from("python")],
+                nickname: None,
+                role: Role::Admin,
+            },
+            User {
+                name: String::from("John"),
+                age: 24,
+                tags: Vec::new(),
+                nickname: Some(String::from("johnny")),
+                role: Role::Guest { expires: 7 },
+            },
+        ];
+
+        for user in users {
+            let ret = metacall::<User>("return_the_argument_py", [user.clone()]).unwrap();
+
+            if ret != user {
+                panic!(
+                    "Invalid return value! Expected `{:#?}` but received `{:#?}`.",
+                    user, ret
+                );
+            }
+        }
+
+        // Values that fail to serialize make the call fail instead of panicking
+        match metacall::<Order>("return_the_argument_py", [Order { id: u64::MAX }]) {
+            Err(MetacallError::FailedConversion(_)) => (),
+            ret => panic!(
+                "Invalid return value! Expected a conversion error but received `{:?}`.",
+                ret
+            ),
+        }
+    }
+}
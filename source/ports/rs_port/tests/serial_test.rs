@@ -0,0 +1,50 @@
+use metacall::{serial, switch, MetacallSerialError, MetacallValue};
+use std::collections::HashMap;
+
+#[test]
+fn serial() {
+    let _d = switch::initialize().unwrap();
+
+    let numbers: Vec<i32> = vec![1, 2, 3];
+    let json = numbers.to_json().unwrap();
+    if json.as_str() != "[1,2,3]" {
+        panic!(
+            "Invalid serialized value! Expected `[1,2,3]` but received `{}`.",
+            json
+        );
+    }
+    let ret = Vec::<i32>::from_json(&json).unwrap();
+    if ret != numbers {
+        panic!(
+            "Invalid deserialized value! Expected `{:?}` but received `{:?}`.",
+            numbers, ret
+        );
+    }
+
+    let user = serial::from_json::<HashMap<String, String>>(r#"{"name": "John"}"#).unwrap();
+    if user.get("name").map(String::as_str) != Some("John") {
+        panic!(
+            "Invalid deserialized value! Expected a map with `John` as name but received `{:?}`.",
+            user
+        );
+    }
+
+    let json = serial::serialize(serial::default_serializer(), String::from("hi")).unwrap();
+    let ret = serial::deserialize::<String>(serial::default_serializer(), json).unwrap();
+    if ret.as_str() != "hi" {
+        panic!("Invalid round trip! Expected `hi` but received `{}`.", ret);
+    }
+
+    if !matches!(
+        serial::from_json::<String>("[1, 2, 3]"),
+        Err(MetacallSerialError::FailedCasting(_))
+    ) {
+        panic!("Deserializing an array as a string must fail with `FailedCasting`!");
+    }
+    if !matches!(
+        serial::from_json_untyped("{ invalid"),
+        Err(MetacallSerialError::DeserializeFailure)
+    ) {
+        panic!("Deserializing an invalid JSON must fail with `DeserializeFailure`!");
+    }
+}
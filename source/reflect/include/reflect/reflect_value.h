@@ -25,6 +25,8 @@
 
 #include <reflect/reflect_api.h>
 
+#include <memory/memory_allocator.h>
+
 #ifdef __cplusplus
 extern "C" {
 #endif
@@ -47,6 +49,19 @@ typedef void (*value_finalizer_cb)(value, void *);
 
 /* -- Methods -- */
 
+/**
+*  @brief
+*    Set the allocator of the values created from now on in the current thread,
+*    each value is deallocated with the allocator it was created with
+*
+*  @param[in] allocator
+*    Allocator of the new values, or null for using the default one
+*
+*  @return
+*    Allocator previously set in the current thread
+*/
+REFLECT_API memory_allocator value_allocator(memory_allocator allocator);
+
 /**
 *  @brief
 *    Reserve memory for a value with size @bytes
@@ -25,6 +25,14 @@
 #include <stdint.h>
 #include <string.h>
 
+/* -- Definitions -- */
+
+#if defined(_MSC_VER)
+	#define VALUE_THREAD_LOCAL __declspec(thread)
+#else
+	#define VALUE_THREAD_LOCAL __thread
+#endif
+
 /* -- Forward Declarations -- */
 
 struct value_impl_type;
@@ -42,6 +50,7 @@ struct value_impl_type
 	size_t ref_count;
 	value_finalizer_cb finalizer;
 	void *finalizer_data;
+	memory_allocator allocator;
 };
 
 /* -- Private Member Data -- */
@@ -49,6 +58,8 @@ struct value_impl_type
 static const char value_impl_magic_alloc[] = "value_impl_magic_alloc";
 static const char value_impl_magic_free[] = "value_impl_magic_free";
 
+static VALUE_THREAD_LOCAL memory_allocator value_impl_allocator = NULL;
+
 /* -- Private Methods -- */
 
 /**
@@ -75,9 +86,19 @@ value_impl value_descriptor(value v)
 	return (value_impl)(((uintptr_t)v) - sizeof(struct value_impl_type));
 }
 
+memory_allocator value_allocator(memory_allocator allocator)
+{
+	memory_allocator previous = value_impl_allocator;
+
+	value_impl_allocator = allocator;
+
+	return previous;
+}
+
 value value_alloc(size_t bytes)
 {
-	value_impl impl = malloc(sizeof(struct value_impl_type) + bytes);
+	memory_allocator allocator = value_impl_allocator;
+	value_impl impl = (allocator == NULL) ? malloc(sizeof(struct value_impl_type) + bytes) : memory_allocator_allocate(allocator, sizeof(struct value_impl_type) + bytes);
 
 	if (impl == NULL)
 	{
@@ -89,6 +110,7 @@ value value_alloc(size_t bytes)
 	impl->ref_count = 1;
 	impl->finalizer = NULL;
 	impl->finalizer_data = NULL;
+	impl->allocator = allocator;
 
 	return (value)(((uintptr_t)impl) + sizeof(struct value_impl_type));
 }
@@ -245,6 +267,13 @@ void value_destroy(value v)
 
 		impl->magic = (uintptr_t)value_impl_magic_free;
 
-		free(impl);
+		if (impl->allocator == NULL)
+		{
+			free(impl);
+		}
+		else
+		{
+			memory_allocator_deallocate(impl->allocator, impl);
+		}
 	}
 }
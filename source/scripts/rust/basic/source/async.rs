@@ -0,0 +1,29 @@
+use std::future;
+
+pub async fn async_add(num_1: i32, num_2: i32) -> i32 {
+    future::ready(num_1).await + future::ready(num_2).await
+}
+
+pub async fn async_greet(name: String) -> String {
+    format!("Hello, {}!", name)
+}
+
+#[repr(C)]
+pub struct Counter {
+    pub count: i32,
+}
+
+impl Counter {
+    pub fn new(count: i32) -> Self {
+        Self { count }
+    }
+    pub fn get_count(&self) -> i32 {
+        self.count
+    }
+    pub async fn async_number() -> i32 {
+        future::ready(456).await
+    }
+    pub async fn async_count(&self) -> i32 {
+        future::ready(self.count).await
+    }
+}
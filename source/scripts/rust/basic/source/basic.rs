@@ -65,8 +65,6 @@ pub fn str_slice(s: &str) -> &str {
     }
 }
 
-// TODO
-/*
 pub fn age_group(age: i32) -> Result<String, String> {
     let valid_age = match age {
         _ if age < 0 => return Err("not born yet".to_string()),
@@ -82,4 +80,11 @@ pub fn age_group(age: i32) -> Result<String, String> {
 
     Ok(result)
 }
-*/
+
+pub fn checked_div(num_1: i32, num_2: i32) -> Option<i32> {
+    num_1.checked_div(num_2)
+}
+
+pub fn greet(name: Option<String>) -> String {
+    format!("Hello, {}!", name.unwrap_or_else(|| "stranger".to_string()))
+}
@@ -0,0 +1,186 @@
+/*
+ *	Loader Library by Parra Studios
+ *	A plugin for loading ruby code at run-time into a process.
+ *
+ *	Copyright (C) 2016 - 2022 Vicente Eduardo Ferrer Garcia <vic798@gmail.com>
+ *
+ *	Licensed under the Apache License, Version 2.0 (the "License");
+ *	you may not use this file except in compliance with the License.
+ *	You may obtain a copy of the License at
+ *
+ *		http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *	Unless required by applicable law or agreed to in writing, software
+ *	distributed under the License is distributed on an "AS IS" BASIS,
+ *	WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *	See the License for the specific language governing permissions and
+ *	limitations under the License.
+ *
+ */
+
+#include <gtest/gtest.h>
+
+#include <metacall/metacall.h>
+
+#include <cstring>
+
+class metacall_rust_async_test : public testing::Test
+{
+protected:
+};
+
+static void *resolve_int(void *result, void *data)
+{
+	int *resolved = static_cast<int *>(data);
+
+	EXPECT_EQ((enum metacall_value_id)METACALL_INT, (enum metacall_value_id)metacall_value_id(result));
+
+	*resolved = metacall_value_to_int(result);
+
+	return NULL;
+}
+
+static void *resolve_string(void *result, void *data)
+{
+	int *resolved = static_cast<int *>(data);
+
+	EXPECT_EQ((enum metacall_value_id)METACALL_STRING, (enum metacall_value_id)metacall_value_id(result));
+	EXPECT_EQ((int)0, (int)strcmp("Hello, World!", metacall_value_to_string(result)));
+
+	*resolved = 1;
+
+	return NULL;
+}
+
+static void *resolve_never(void *, void *)
+{
+	int never_executed = 0;
+	EXPECT_EQ((int)1, (int)never_executed);
+
+	return NULL;
+}
+
+static void *reject_never(void *, void *)
+{
+	int never_executed = 0;
+	EXPECT_EQ((int)1, (int)never_executed);
+
+	return NULL;
+}
+
+static void *reject_throwable(void *result, void *data)
+{
+	int *rejected = static_cast<int *>(data);
+
+	EXPECT_EQ((enum metacall_value_id)METACALL_THROWABLE, (enum metacall_value_id)metacall_value_id(result));
+
+	*rejected = 1;
+
+	return NULL;
+}
+
+TEST_F(metacall_rust_async_test, DefaultConstructor)
+{
+	const char *rs_scripts[] = {
+		"async.rs"
+	};
+
+	ASSERT_EQ((int)0, (int)metacall_initialize());
+
+	EXPECT_EQ((int)0, (int)metacall_load_from_file("rs", rs_scripts, sizeof(rs_scripts) / sizeof(rs_scripts[0]), NULL));
+
+	/* Test for asyncness introspection */
+	EXPECT_EQ((int)1, (int)metacall_function_async(metacall_function("async_add")));
+	EXPECT_EQ((int)1, (int)metacall_function_async(metacall_function("async_greet")));
+
+	/* Test await of async functions, the future is driven to completion before returning */
+	{
+		void *args[] = {
+			metacall_value_create_int(5),
+			metacall_value_create_int(10)
+		};
+
+		int resolved = 0;
+
+		metacall_await_s("async_add", args, sizeof(args) / sizeof(args[0]), &resolve_int, &reject_never, &resolved);
+
+		EXPECT_EQ((int)15, (int)resolved);
+
+		metacall_value_destroy(args[0]);
+		metacall_value_destroy(args[1]);
+	}
+	{
+		void *args[] = {
+			metacall_value_create_string("World", sizeof("World") - 1)
+		};
+
+		int resolved = 0;
+
+		metacall_await_s("async_greet", args, sizeof(args) / sizeof(args[0]), &resolve_string, &reject_never, &resolved);
+
+		EXPECT_EQ((int)1, (int)resolved);
+
+		metacall_value_destroy(args[0]);
+	}
+
+	/* Test calling async functions, which return a future that can be awaited only once */
+	{
+		void *future = metacall("async_add", 3, 4);
+
+		ASSERT_EQ((enum metacall_value_id)METACALL_FUTURE, (enum metacall_value_id)metacall_value_id(future));
+
+		int resolved = 0;
+
+		metacall_await_future(metacall_value_to_future(future), &resolve_int, &reject_never, &resolved);
+
+		EXPECT_EQ((int)7, (int)resolved);
+
+		int rejected = 0;
+
+		metacall_await_future(metacall_value_to_future(future), &resolve_never, &reject_throwable, &rejected);
+
+		EXPECT_EQ((int)1, (int)rejected);
+
+		metacall_value_destroy(future);
+	}
+
+	/* Test async static methods */
+	{
+		void *counter_class = metacall_class("Counter");
+		ASSERT_NE((void *)NULL, (void *)counter_class);
+
+		void *future = metacallv_class(counter_class, "async_number", nullptr, 0);
+
+		ASSERT_EQ((enum metacall_value_id)METACALL_FUTURE, (enum metacall_value_id)metacall_value_id(future));
+
+		int resolved = 0;
+
+		metacall_await_future(metacall_value_to_future(future), &resolve_int, &reject_never, &resolved);
+
+		EXPECT_EQ((int)456, (int)resolved);
+
+		metacall_value_destroy(future);
+	}
+
+	/* Test async methods taking self, which are registered but throw when called */
+	{
+		void *counter_class = metacall_class("Counter");
+		ASSERT_NE((void *)NULL, (void *)counter_class);
+
+		void *constructor_params[] = {
+			metacall_value_create_int(789)
+		};
+		void *counter_v = metacall_class_new(counter_class, "counter", constructor_params, sizeof(constructor_params) / sizeof(constructor_params[0]));
+		metacall_value_destroy(constructor_params[0]);
+		void *counter = metacall_value_to_object(counter_v);
+
+		void *ret = metacallv_object(counter, "async_count", nullptr, 0);
+
+		EXPECT_EQ((enum metacall_value_id)METACALL_THROWABLE, (enum metacall_value_id)metacall_value_id(ret));
+
+		metacall_value_destroy(ret);
+		metacall_value_destroy(counter_v);
+	}
+
+	EXPECT_EQ((int)0, (int)metacall_destroy());
+}
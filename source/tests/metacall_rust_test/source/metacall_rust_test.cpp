@@ -154,17 +154,53 @@ TEST_F(metacall_rust_test, DefaultConstructor)
 		metacall_value_destroy(ret);
 	}
 
-	/* TODO */
-	/*
 	{
 		// test if we can return result
 		void *ret = metacall("age_group", 21);
+		EXPECT_EQ((enum metacall_value_id)METACALL_STRING, (enum metacall_value_id)metacall_value_id(ret));
+		EXPECT_EQ((int)0, (int)strcmp(metacall_value_to_string(ret), "adult"));
+		metacall_value_destroy(ret);
+	}
+
+	{
+		// test if the error of the result is thrown
+		void *ret = metacall("age_group", -1);
+		EXPECT_EQ((enum metacall_value_id)METACALL_THROWABLE, (enum metacall_value_id)metacall_value_id(ret));
+
+		struct metacall_exception_type ex;
+
+		EXPECT_EQ((int)0, (int)metacall_error_from_value(ret, &ex));
+		EXPECT_EQ((int)0, (int)strcmp("not born yet", ex.message));
+		metacall_value_destroy(ret);
+	}
+
+	{
+		// test if we can return option
+		void *ret = metacall("checked_div", 10, 2);
+		EXPECT_EQ((int)5, (int)metacall_value_to_int(ret));
+		metacall_value_destroy(ret);
 
-		// TODO
+		ret = metacall("checked_div", 10, 0);
+		EXPECT_EQ((enum metacall_value_id)METACALL_NULL, (enum metacall_value_id)metacall_value_id(ret));
+		metacall_value_destroy(ret);
+	}
+
+	{
+		// test if we can pass option
+		void *args[] = {
+			metacall_value_create_null()
+		};
+		void *ret = metacallv_s("greet", args, 1);
+		EXPECT_EQ((int)0, (int)strcmp(metacall_value_to_string(ret), "Hello, stranger!"));
+		metacall_value_destroy(args[0]);
+		metacall_value_destroy(ret);
 
+		args[0] = metacall_value_create_string("Rust", sizeof("Rust") - 1);
+		ret = metacallv_s("greet", args, 1);
+		EXPECT_EQ((int)0, (int)strcmp(metacall_value_to_string(ret), "Hello, Rust!"));
+		metacall_value_destroy(args[0]);
 		metacall_value_destroy(ret);
 	}
-	*/
 
 	/* Print inspect information */
 	{